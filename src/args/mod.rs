@@ -1,7 +1,12 @@
+#[cfg(test)]
+mod tests;
+
 use std::{ffi::{OsStr, OsString}, os::unix::ffi::OsStrExt, path::{Path, PathBuf}};
 
 use clap::{AppSettings, clap_app, crate_authors, crate_description, crate_version};
 
+use crate::diagnostics;
+
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub enum Command {
@@ -11,9 +16,21 @@ pub enum Command {
 }
 
 
+/// Where to read the script source code from.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Script {
+	/// Read from the standard input.
+	Stdin,
+	/// Read from a file.
+	Path(PathBuf),
+	/// Inline source code, supplied in the command line.
+	Command(Box<[u8]>),
+}
+
+
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct Args {
-	pub script_path: Option<PathBuf>,
+	pub script: Script,
 	/// Check program with static analysis, but don't run.
 	pub check: bool,
 	/// Print the lexemes.
@@ -22,6 +39,8 @@ pub struct Args {
 	pub print_ast: bool,
 	/// Print the program.
 	pub print_program: bool,
+	/// How to report errors.
+	pub diagnostics_format: diagnostics::Format,
 	/// Arguments for the script.
 	pub script_args: Box<[Box<[u8]>]>
 }
@@ -42,6 +61,10 @@ where
 				(@arg lex: --lex "Print the lexemes")
 				(@arg ast: --ast "Print the AST")
 				(@arg program: --program "Print the PROGAM")
+				(@arg command: -c --command +takes_value "Execute the given source code")
+				(@arg diagnostics_format: --("diagnostics-format") +takes_value
+					possible_value[text json]
+					"Format of the reported errors")
 				// The script path must not be a separate parameter because we must prevent clap
 				// from parsing flags to the right of the script path.
 				(@arg arguments: ... +allow_hyphen_values "Script and/or arguments")
@@ -57,16 +80,21 @@ where
 				.map(OsStrExt::as_bytes);
 
 			let mut script_args = Vec::new();
-			let script_path = match arguments.next() {
-				None => None,
-				Some(b"-") => None,
-				Some(arg) => {
-					let path = Path::new(OsStr::from_bytes(arg));
-					if path.is_file() {
-						Some(path.to_owned())
-					} else {
-						script_args.push(arg.into());
-						None
+			let script = match matches.value_of_os("command") {
+				// When the source is inline, all arguments are passed to the script.
+				Some(command) => Script::Command(command.as_bytes().into()),
+
+				None => match arguments.next() {
+					None => Script::Stdin,
+					Some(b"-") => Script::Stdin,
+					Some(arg) => {
+						let path = Path::new(OsStr::from_bytes(arg));
+						if path.is_file() {
+							Script::Path(path.to_owned())
+						} else {
+							script_args.push(arg.into());
+							Script::Stdin
+						}
 					}
 				}
 			};
@@ -76,11 +104,15 @@ where
 			Ok(
 				Command::Run(
 					Args {
-						script_path,
+						script,
 						check: matches.is_present("check"),
 						print_lexemes: matches.is_present("lex"),
 						print_ast: matches.is_present("ast"),
 						print_program: matches.is_present("program"),
+						diagnostics_format: match matches.value_of("diagnostics_format") {
+							Some("json") => diagnostics::Format::Json,
+							_ => diagnostics::Format::Text,
+						},
 						script_args: script_args.into_boxed_slice(),
 					}
 				)
@@ -0,0 +1,41 @@
+use super::{parse, Command, Script};
+
+
+/// Parse the given command line arguments, which must produce a run command.
+fn parse_run(args: &[&str]) -> super::Args {
+	match parse(args.iter().copied()) {
+		Ok(Command::Run(args)) => args,
+		result => panic!("expected run command, got {:?}", result),
+	}
+}
+
+
+#[test]
+fn test_command() {
+	let args = parse_run(&["hush", "-c", "std.print(1)"]);
+
+	assert_eq!(args.script, Script::Command(b"std.print(1)".to_vec().into_boxed_slice()));
+	assert!(args.script_args.is_empty());
+}
+
+
+#[test]
+fn test_command_args() {
+	let args = parse_run(&["hush", "--command", "std.print(std.args())", "src", "-x"]);
+
+	assert_eq!(args.script, Script::Command(b"std.print(std.args())".to_vec().into_boxed_slice()));
+	assert_eq!(
+		args.script_args,
+		vec![b"src".to_vec().into_boxed_slice(), b"-x".to_vec().into_boxed_slice()]
+			.into_boxed_slice()
+	);
+}
+
+
+#[test]
+fn test_command_missing_value() {
+	match parse(["hush", "-c"].iter().copied()) {
+		Err(error) => assert_eq!(error.kind, clap::ErrorKind::EmptyValue),
+		result => panic!("expected error, got {:?}", result),
+	}
+}
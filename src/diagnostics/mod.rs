@@ -0,0 +1,261 @@
+#[cfg(test)]
+mod tests;
+
+use serde_json::json;
+
+use crate::{
+	fmt::{self, FmtString},
+	runtime::{Panic, PanicDisplayContext},
+	semantic,
+	symbol::{self, Symbol},
+	syntax::{self, SourceMap},
+};
+
+
+/// Max number of reported errors in text format.
+const MAX_ERRORS: usize = 20;
+
+
+/// The format in which diagnostics are reported.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Format {
+	/// Human readable text, colored if the output is a terminal.
+	#[default]
+	Text,
+	/// One JSON object per line, for editors and other tools.
+	Json,
+}
+
+
+/// Report syntax errors to stderr.
+pub fn syntax_errors(
+	errors: &syntax::Errors,
+	source: &syntax::Source,
+	format: Format,
+	interner: &symbol::Interner,
+	sources: &SourceMap,
+) {
+	match format {
+		Format::Text => eprint!(
+			"{}",
+			fmt::Show(
+				errors,
+				syntax::AnalysisDisplayContext {
+					max_errors: Some(MAX_ERRORS),
+					interner,
+					sources,
+				}
+			)
+		),
+
+		Format::Json => {
+			for diagnostic in syntax_errors_json(errors, source, interner) {
+				eprintln!("{}", diagnostic);
+			}
+		}
+	}
+}
+
+
+/// Convert syntax errors to JSON objects. Errors without a position, like unexpected
+/// EOF, are reported at the end of the source.
+fn syntax_errors_json(
+	errors: &syntax::Errors,
+	source: &syntax::Source,
+	interner: &symbol::Interner
+) -> Vec<serde_json::Value> {
+	errors.0
+		.iter()
+		.map(
+			|error| match error {
+				syntax::Error::Lexer(error) => Diagnostic {
+					kind: "lexer",
+					message: error.error.to_string(),
+					pos: error.pos,
+				},
+
+				syntax::Error::Parser(error) => Diagnostic {
+					kind: "parser",
+					message: error.message().fmt_string(interner),
+					pos: error.pos().unwrap_or_else(|| source.end_pos()),
+				},
+			}
+		)
+		.map(|diagnostic| diagnostic.to_json(interner))
+		.collect()
+}
+
+
+/// Report semantic errors to stderr.
+pub fn semantic_errors(
+	errors: &semantic::Errors,
+	format: Format,
+	interner: &symbol::Interner,
+	sources: &SourceMap,
+) {
+	match format {
+		Format::Text => eprint!(
+			"{}",
+			fmt::Show(
+				errors,
+				semantic::ErrorsDisplayContext {
+					max_errors: Some(MAX_ERRORS),
+					interner,
+					sources,
+				}
+			)
+		),
+
+		Format::Json => {
+			for diagnostic in semantic_errors_json(errors, interner) {
+				eprintln!("{}", diagnostic);
+			}
+		}
+	}
+}
+
+
+/// Convert semantic errors to JSON objects.
+fn semantic_errors_json(
+	errors: &semantic::Errors,
+	interner: &symbol::Interner
+) -> Vec<serde_json::Value> {
+	errors.0
+		.iter()
+		.map(
+			|error| Diagnostic {
+				kind: "semantic",
+				message: error.kind.fmt_string(interner),
+				pos: error.pos,
+			}
+			.to_json(interner)
+		)
+		.collect()
+}
+
+
+/// Report semantic warnings to stderr.
+pub fn semantic_warnings(
+	warnings: &semantic::Warnings,
+	format: Format,
+	interner: &symbol::Interner,
+	sources: &SourceMap,
+) {
+	match format {
+		Format::Text => eprint!(
+			"{}",
+			fmt::Show(
+				warnings,
+				semantic::WarningsDisplayContext {
+					max_warnings: Some(MAX_ERRORS),
+					interner,
+					sources,
+				}
+			)
+		),
+
+		Format::Json => {
+			for diagnostic in semantic_warnings_json(warnings, interner) {
+				eprintln!("{}", diagnostic);
+			}
+		}
+	}
+}
+
+
+/// Convert semantic warnings to JSON objects.
+fn semantic_warnings_json(
+	warnings: &semantic::Warnings,
+	interner: &symbol::Interner
+) -> Vec<serde_json::Value> {
+	warnings.0
+		.iter()
+		.map(
+			|warning| Diagnostic {
+				kind: "warning",
+				message: warning.kind.to_string(),
+				pos: warning.pos,
+			}
+			.to_json(interner)
+		)
+		.collect()
+}
+
+
+/// Report a panic to stderr.
+pub fn panic(panic: &Panic, format: Format, interner: &symbol::Interner, sources: &SourceMap) {
+	match format {
+		Format::Text => eprintln!("{}", fmt::Show(panic, PanicDisplayContext { interner, sources })),
+		Format::Json => eprintln!("{}", panic_json(panic, interner)),
+	}
+}
+
+
+/// Convert a panic to a JSON object, including the backtrace.
+fn panic_json(panic: &Panic, interner: &symbol::Interner) -> serde_json::Value {
+	let diagnostic = Diagnostic {
+		kind: "panic",
+		message: panic.kind.message().fmt_string(interner),
+		pos: panic.kind.pos().into(),
+	};
+
+	let backtrace: Vec<_> = panic.backtrace
+		.iter()
+		.map(
+			|frame| json!({
+				"function": frame.function.fmt_string(interner),
+				"path": path(frame.call_site.path, interner),
+				"line": frame.call_site.line,
+				"column": frame.call_site.column,
+			})
+		)
+		.collect();
+
+	let mut object = diagnostic.to_json(interner);
+	object["backtrace"] = backtrace.into();
+
+	object
+}
+
+
+/// A diagnostic in a format independent way.
+#[derive(Debug)]
+struct Diagnostic {
+	/// The kind of diagnostic: lexer, parser, semantic, warning or panic.
+	kind: &'static str,
+	/// The error message, without the position.
+	message: String,
+	/// The start of the span, along with its length.
+	pos: syntax::SourcePos,
+}
+
+
+impl Diagnostic {
+	/// Convert to a JSON object. An unknown span end is null.
+	fn to_json(&self, interner: &symbol::Interner) -> serde_json::Value {
+		let pos = self.pos;
+
+		let end = (pos.len != 0).then(
+			|| json!({ "line": pos.line, "column": pos.column + pos.len })
+		);
+
+		json!({
+			"kind": self.kind,
+			"message": self.message,
+			"path": path(pos.path, interner),
+			"line": pos.line,
+			"column": pos.column,
+			"end": end,
+		})
+	}
+}
+
+
+/// Resolve a path symbol to a string.
+fn path(symbol: Symbol, interner: &symbol::Interner) -> String {
+	interner
+		.resolve(symbol)
+		.map(String::from_utf8_lossy)
+		.unwrap_or_default()
+		.into_owned()
+}
@@ -0,0 +1,136 @@
+use serde_json::json;
+
+use crate::{
+	runtime::{Panic, SourcePos},
+	semantic,
+	symbol,
+	syntax,
+};
+use super::{
+	panic_json,
+	semantic_errors_json,
+	semantic_warnings_json,
+	syntax_errors_json,
+};
+
+
+/// Perform syntax analysis on the given source code, producing the JSON diagnostics.
+fn syntax_diagnostics(contents: &str) -> Vec<serde_json::Value> {
+	let mut interner = symbol::Interner::new();
+	let path = interner.get_or_intern("<test>");
+	let source = syntax::Source { path, contents: contents.as_bytes().into() };
+
+	let analysis = syntax::Analysis::analyze(&source, &mut interner);
+
+	syntax_errors_json(&analysis.errors, &source, &interner)
+}
+
+
+/// Perform syntax and semantic analysis on the given source code, producing the JSON
+/// diagnostics for either the errors or the warnings.
+fn semantic_diagnostics(contents: &str) -> Vec<serde_json::Value> {
+	let mut interner = symbol::Interner::new();
+	let path = interner.get_or_intern("<test>");
+	let source = syntax::Source { path, contents: contents.as_bytes().into() };
+
+	let analysis = syntax::Analysis::analyze(&source, &mut interner);
+	assert!(analysis.is_ok());
+
+	match semantic::Analyzer::analyze(analysis.ast, &mut interner) {
+		Ok(program) => semantic_warnings_json(&program.warnings, &interner),
+		Err(errors) => semantic_errors_json(&errors, &interner),
+	}
+}
+
+
+#[test]
+fn test_lexer_error() {
+	assert_eq!(
+		syntax_diagnostics("let x = 1\nlet y = `2"),
+		vec![
+			json!({
+				"kind": "lexer",
+				"message": "unexpected '`'",
+				"path": "<test>",
+				"line": 2,
+				"column": 8,
+				"end": { "line": 2, "column": 9 },
+			})
+		]
+	);
+}
+
+
+#[test]
+fn test_parser_error() {
+	assert_eq!(
+		syntax_diagnostics("let x = 1\nlet y = x +"),
+		vec![
+			json!({
+				"kind": "parser",
+				"message": "unexpected end of file",
+				"path": "<test>",
+				"line": 2,
+				"column": 11,
+				"end": null,
+			})
+		]
+	);
+}
+
+
+#[test]
+fn test_semantic_error() {
+	assert_eq!(
+		semantic_diagnostics("let x = 1\nx + undeclared"),
+		vec![
+			json!({
+				"kind": "semantic",
+				"message": "undeclared variable 'undeclared'",
+				"path": "<test>",
+				"line": 2,
+				"column": 4,
+				"end": { "line": 2, "column": 14 },
+			})
+		]
+	);
+}
+
+
+#[test]
+fn test_warning() {
+	assert_eq!(
+		semantic_diagnostics("match 1 with\n\tcase _ then 1\n\tcase 2 then 2\nend"),
+		vec![
+			json!({
+				"kind": "warning",
+				"message": "unreachable match arm",
+				"path": "<test>",
+				"line": 3,
+				"column": 1,
+				"end": { "line": 3, "column": 5 },
+			})
+		]
+	);
+}
+
+
+#[test]
+fn test_panic() {
+	let mut interner = symbol::Interner::new();
+	let path = interner.get_or_intern("<test>");
+	let panic = Panic::assertion_failed(SourcePos { line: 3, column: 2, len: 0, path });
+
+	assert_eq!(
+		panic_json(&panic, &interner),
+		json!({
+			"kind": "panic",
+			"message": "assertion failed",
+			"path": "<test>",
+			"line": 3,
+			"column": 2,
+			"end": null,
+			"backtrace": [],
+		})
+	);
+}
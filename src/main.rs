@@ -1,6 +1,7 @@
 #![allow(dead_code)] // This is temporarily used for the inital development.
 
 mod args;
+mod diagnostics;
 mod fmt;
 mod io;
 mod runtime;
@@ -13,10 +14,14 @@ mod tests;
 
 use std::os::unix::ffi::OsStrExt;
 
-use term::color;
+use term::{
+	color,
+	editor::{Editor, ReadLine},
+};
 
-use args::{Args, Command};
-use runtime::{Panic, SourcePos, Runtime};
+use args::{Args, Command, Script};
+use runtime::{value::Value, Panic, SourcePos, Runtime};
+use syntax::SourceMap;
 
 
 #[derive(Debug)]
@@ -62,48 +67,65 @@ fn main() -> ! {
 
 
 fn run(args: Args) -> ExitStatus {
+	if args.script == Script::Stdin && termion::is_tty(&std::io::stdin()) {
+		return repl(args);
+	}
+
+	let diagnostics_format = args.diagnostics_format;
+	if diagnostics_format == diagnostics::Format::Json {
+		color::disable();
+	}
+
 	let mut interner = symbol::Interner::new();
 
-	let (source, path) = match args.script_path {
-		Some(path) => {
+	let (source, path) = match args.script {
+		Script::Path(path) => {
 			let path = interner.get_or_intern(path.as_os_str().as_bytes());
 			let source = syntax::Source::from_path(path, &mut interner);
 			(source, path)
 		},
 
-		None => {
+		Script::Stdin => {
 			let path = interner.get_or_intern("<stdin>");
 			let source = syntax::Source::from_reader(path, std::io::stdin().lock());
 			(source, path)
 		},
+
+		Script::Command(command) => {
+			let path = interner.get_or_intern("<command-line>");
+			let source = syntax::Source { path, contents: command };
+			(Ok(source), path)
+		},
 	};
 
 	let source = match source {
     Ok(source) => source,
     Err(error) => {
-			eprintln!(
-				"{}",
-				fmt::Show(
-					Panic::io(error, SourcePos::file(path)),
-					&interner
-				)
+			diagnostics::panic(
+				&Panic::io(error, SourcePos::file(path)),
+				diagnostics_format,
+				&interner,
+				&SourceMap::default(),
 			);
 			return ExitStatus::Panic;
 		}
 	};
 
+	let mut sources = SourceMap::default();
+	sources.insert(&source);
+
 	// ----------------------------------------------------------------------------------------
 	let syntactic_analysis = syntax::Analysis::analyze(&source, &mut interner);
 	let has_syntax_errors = !syntactic_analysis.is_ok();
 
 	if has_syntax_errors {
-		eprint!("{}", fmt::Show(
-			syntactic_analysis.errors,
-			syntax::AnalysisDisplayContext {
-				max_errors: Some(20),
-				interner: &interner,
-			}
-		));
+		diagnostics::syntax_errors(
+			&syntactic_analysis.errors,
+			&source,
+			diagnostics_format,
+			&interner,
+			&sources,
+		);
 	}
 
 	if args.print_lexemes {
@@ -138,17 +160,13 @@ fn run(args: Args) -> ExitStatus {
 	let program = match semantic::Analyzer::analyze(syntactic_analysis.ast, &mut interner) {
 		Ok(program) => program,
 		Err(errors) => {
-			eprint!("{}", fmt::Show(
-				errors,
-				semantic::ErrorsDisplayContext {
-					max_errors: Some(20),
-					interner: &interner,
-				}
-			));
+			diagnostics::semantic_errors(&errors, diagnostics_format, &interner, &sources);
 			return ExitStatus::StaticError;
 		}
 	};
 
+	diagnostics::semantic_warnings(&program.warnings, diagnostics_format, &interner, &sources);
+
 	if args.print_program {
 		println!("{}", color::Fg(color::Yellow, "--------------------------------------------------"));
 		println!(
@@ -175,12 +193,147 @@ fn run(args: Args) -> ExitStatus {
 		args.script_args.into_vec(), // Use vec's owned iterator.
 		interner
 	);
+	runtime.set_diagnostics_format(diagnostics_format);
+	runtime.set_sources(sources);
 
 	match runtime.eval(program) {
     Ok(_) => ExitStatus::Success,
     Err(panic) => {
-			eprintln!("{}", fmt::Show(panic, runtime.interner()));
+			diagnostics::panic(&panic, diagnostics_format, runtime.interner(), runtime.sources());
 			ExitStatus::Panic
 		}
 	}
 }
+
+
+/// Run an interactive session, reading the input from the terminal.
+/// A single runtime is kept alive for the whole session, and therefore global variables
+/// persist between inputs.
+fn repl(args: Args) -> ExitStatus {
+	const PROMPT: &str = "hush> ";
+	const CONTINUATION_PROMPT: &str = "  ... ";
+
+	let diagnostics_format = args.diagnostics_format;
+	if diagnostics_format == diagnostics::Format::Json {
+		color::disable();
+	}
+
+	let mut interner = symbol::Interner::new();
+	let path = interner.get_or_intern("<repl>");
+	let mut session = semantic::Session::new(&mut interner);
+
+	let mut runtime = Runtime::new(
+		args.script_args.into_vec(), // Use vec's owned iterator.
+		interner
+	);
+	runtime.set_diagnostics_format(diagnostics_format);
+
+	let mut editor = Editor::new();
+	let mut input = String::new();
+	let mut inputs = 0_usize;
+
+	loop {
+		let prompt = if input.is_empty() { PROMPT } else { CONTINUATION_PROMPT };
+
+		match editor.read_line(prompt) {
+			Ok(ReadLine::Line(line)) => {
+				editor.add_history(&line);
+				input.push_str(&line);
+				input.push('\n');
+			}
+
+			Ok(ReadLine::Interrupted) => {
+				input.clear();
+				continue;
+			}
+
+			Ok(ReadLine::Eof) => return ExitStatus::Success,
+
+			Err(error) => {
+				diagnostics::panic(
+					&Panic::io(error, SourcePos::file(path)),
+					diagnostics_format,
+					runtime.interner(),
+					runtime.sources(),
+				);
+				return ExitStatus::Panic;
+			}
+		}
+
+		if input.trim().is_empty() {
+			input.clear();
+			continue;
+		}
+
+		// Each input gets its own path, so that snippets of functions defined by previous
+		// inputs are still found in the source map.
+		inputs += 1;
+		let input_path = runtime.interner_mut().get_or_intern(format!("<repl:{}>", inputs));
+		let source = syntax::Source { path: input_path, contents: input.as_bytes().into() };
+		runtime.sources_mut().insert(&source);
+
+		// ----------------------------------------------------------------------------------------
+		let syntactic_analysis = syntax::Analysis::analyze(&source, runtime.interner_mut());
+
+		if !syntactic_analysis.is_ok() {
+			// Open blocks and literals may be completed by the following lines.
+			if syntactic_analysis.errors.is_incomplete() {
+				continue;
+			}
+
+			diagnostics::syntax_errors(
+				&syntactic_analysis.errors,
+				&source,
+				diagnostics_format,
+				runtime.interner(),
+				runtime.sources(),
+			);
+
+			input.clear();
+			continue;
+		}
+
+		input.clear();
+
+		// ----------------------------------------------------------------------------------------
+		let program = semantic::Analyzer::analyze_session(
+			syntactic_analysis.ast,
+			runtime.interner_mut(),
+			&mut session,
+		);
+
+		let program = match program {
+			Ok(program) => program,
+			Err(errors) => {
+				diagnostics::semantic_errors(
+					&errors,
+					diagnostics_format,
+					runtime.interner(),
+					runtime.sources(),
+				);
+				continue;
+			}
+		};
+
+		diagnostics::semantic_warnings(
+			&program.warnings,
+			diagnostics_format,
+			runtime.interner(),
+			runtime.sources(),
+		);
+
+		// ----------------------------------------------------------------------------------------
+		let program = Box::leak(Box::new(program));
+
+		match runtime.eval_session(program) {
+			Ok(Value::Nil) => (),
+			Ok(value) => println!("{}", fmt::Show(value, runtime.interner())),
+			Err(panic) => diagnostics::panic(
+				&panic,
+				diagnostics_format,
+				runtime.interner(),
+				runtime.sources(),
+			),
+		}
+	}
+}
@@ -0,0 +1,51 @@
+use std::{
+	collections::BTreeMap,
+	ffi::OsStr,
+	sync::{Arc, Mutex, MutexGuard},
+};
+
+
+/// The words an alias expands to. The first one is the program.
+pub type Alias = Box<[Box<OsStr>]>;
+
+
+/// The table of command aliases, shared between the runtime and the command blocks.
+/// Aliases are expanded when a command block is built, therefore an alias defined in
+/// a block takes effect only in subsequent blocks.
+#[derive(Debug, Default, Clone)]
+pub struct Aliases(Arc<Mutex<BTreeMap<Box<OsStr>, Alias>>>);
+
+
+impl Aliases {
+	/// Get the expansion of the given alias.
+	pub fn get(&self, name: &OsStr) -> Option<Alias> {
+		self.table().get(name).cloned()
+	}
+
+
+	/// Define an alias, replacing any previous definition.
+	pub fn insert(&self, name: Box<OsStr>, alias: Alias) {
+		self.table().insert(name, alias);
+	}
+
+
+	/// Remove an alias, returning whether it was defined.
+	pub fn remove(&self, name: &OsStr) -> bool {
+		self.table().remove(name).is_some()
+	}
+
+
+	/// Get all aliases, sorted by name.
+	pub fn list(&self) -> Vec<(Box<OsStr>, Alias)> {
+		self.table()
+			.iter()
+			.map(|(name, alias)| (name.clone(), alias.clone()))
+			.collect()
+	}
+
+
+	fn table(&self) -> MutexGuard<'_, BTreeMap<Box<OsStr>, Alias>> {
+		// The lock is never held while calling code which could panic.
+		self.0.lock().expect("alias table poisoned")
+	}
+}
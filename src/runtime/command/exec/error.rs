@@ -4,7 +4,6 @@ use std::{
 };
 
 use crate::{
-	io::FileDescriptor,
 	term::color,
 	symbol, runtime::value::{self, Value}, fmt::Show,
 };
@@ -19,11 +18,6 @@ pub enum Panic {
 		items: u32,
 		pos: SourcePos,
 	},
-	/// Redirection of the given file descriptor is currently unsupported.
-	UnsupportedFileDescriptor {
-		fd: FileDescriptor,
-		pos: SourcePos,
-	},
 	/// Currently, Hush requires patterns to be valid UTF-8.
 	InvalidPattern {
 		pattern: OsString,
@@ -38,11 +32,6 @@ impl Panic {
 		Self::InvalidArgs { object, items, pos }
 	}
 
-	/// Redirection of the given file descriptor is currently unsupported.
-	pub fn unsupported_fd(fd: FileDescriptor, pos: SourcePos) -> Self {
-		Self::UnsupportedFileDescriptor { fd, pos }
-	}
-
 	/// Currently, Hush requires patterns to be valid UTF-8.
 	pub fn invalid_pattern(pattern: OsString, pos: SourcePos) -> Self {
 		Self::InvalidPattern { pattern, pos }
@@ -65,14 +54,6 @@ impl std::fmt::Display for Panic {
 					items
 				),
 
-			Self::UnsupportedFileDescriptor { fd, .. } =>
-				write!(
-					f,
-					"{}: unsupported file descriptor ({})",
-					panic,
-					color::Fg(color::Yellow, fd)
-				),
-
 			Self::InvalidPattern { pattern, .. } =>
 				write!(
 					f,
@@ -94,7 +75,6 @@ impl From<Panic> for crate::runtime::Panic {
 
 		match panic {
 			Panic::InvalidArgs { object, items, pos } => P::invalid_command_args(object, items, pos),
-			Panic::UnsupportedFileDescriptor { fd, pos } => P::unsupported_fd(fd, pos),
 			Panic::InvalidPattern { pattern, pos } => P::invalid_pattern(pattern, pos),
 		}
 	}
@@ -109,6 +89,7 @@ impl IntoValue for ErrorStatus {
 		thread_local! {
 			pub static STATUS: Value = "status".into();
 			pub static POS: Value = "pos".into();
+			pub static TIMEOUT: Value = "timeout".into();
 		}
 
 		let description = std::mem::take(&mut self.description).into();
@@ -117,6 +98,9 @@ impl IntoValue for ErrorStatus {
 		STATUS.with(
 			|status| context.insert(status.copy(), Value::Int(self.status as i64))
 		);
+		TIMEOUT.with(
+			|timeout| context.insert(timeout.copy(), Value::Bool(self.timeout))
+		);
 		POS.with(
 			|pos| context.insert(pos.copy(), Show(self.pos, interner).to_string().into())
 		);
@@ -29,7 +29,9 @@ impl Display for Argument {
 impl Display for RedirectionTarget {
 	fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
 		match self {
-			Self::Fd(fd) => write!(f, ">{}", fd),
+			Self::Fd(fd) => write!(f, ">&{}", fd),
+
+			Self::Close => ">&-".fmt(f),
 
 			Self::Overwrite(arg) => {
 				">".fmt(f)?;
@@ -53,12 +55,14 @@ impl Display for Redirection {
 				target.fmt(f)
 			}
 
-			Self::Input { literal: false, source } => {
+			Self::Input { target, literal: false, source } => {
+				target.fmt(f)?;
 				"<".fmt(f)?;
 				source.fmt(f)
 			}
 
-			Self::Input { literal: true, source } => {
+			Self::Input { target, literal: true, source } => {
+				target.fmt(f)?;
 				"<<".fmt(f)?;
 				source.fmt(f)
 			}
@@ -70,8 +74,9 @@ impl Display for Redirection {
 impl Display for Builtin {
 	fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
 		let command = match self {
-			Self::Alias => "alias",
+			Self::Alias(_) => "alias",
 			Self::Cd => "cd",
+			Self::Unalias(_) => "unalias",
 		};
 
 		color::Fg(color::Green, command).fmt(f)
@@ -0,0 +1,365 @@
+use std::{
+	cell::RefCell,
+	collections::HashMap,
+	sync::{Arc, Mutex},
+	thread,
+	time::{Duration, Instant},
+};
+
+use gc::{Finalize, Gc, Trace};
+
+use crate::runtime::{
+	signal,
+	value::{CallContext, Dict, NativeFun, Value},
+	Panic as RuntimePanic,
+};
+
+use super::{Panic, PipelineErrors, IntoValue, POLL_INTERVAL};
+
+
+type Thread = thread::JoinHandle<Result<Box<[PipelineErrors]>, Panic>>;
+
+
+/// The processes of a command block which are currently running. This is shared with
+/// the thread executing the block.
+#[derive(Debug, Default)]
+pub struct Job(Mutex<Processes>);
+
+
+#[derive(Debug, Default)]
+struct Processes {
+	/// The running processes.
+	pids: Vec<u32>,
+	/// Signals sent while no process was running, to be delivered to the next one.
+	pending: Vec<libc::c_int>,
+	/// Whether the block has started its last command, after which no processes are
+	/// spawned.
+	last: bool,
+}
+
+
+impl Job {
+	/// Register a spawned process, delivering the pending signals to it.
+	pub fn spawned(&self, pid: u32) {
+		let mut processes = self.processes();
+
+		for signal in processes.pending.drain(..) {
+			// Ignore failures, as the process might have exited meanwhile.
+			let _ = Self::send(pid, signal);
+		}
+
+		processes.pids.push(pid);
+	}
+
+
+	/// Unregister a process which has been waited.
+	pub fn reaped(&self, pid: u32) {
+		self.processes().pids.retain(|&running| running != pid);
+	}
+
+
+	/// Mark that the block is starting its last command.
+	pub fn last_command(&self) {
+		self.processes().last = true;
+	}
+
+
+	fn pids(&self) -> Vec<u32> {
+		self.processes().pids.clone()
+	}
+
+
+	/// Send a signal to the running processes and their descendants. If there are none,
+	/// the signal is delivered to the next spawned process, unless the block has already
+	/// started its last command.
+	/// Returns whether the signal was delivered or queued.
+	pub fn kill(&self, signal: libc::c_int) -> bool {
+		let mut processes = self.processes();
+
+		if processes.pids.is_empty() {
+			if processes.last {
+				return false;
+			}
+
+			processes.pending.push(signal);
+			return true;
+		}
+
+		let mut delivered = false;
+
+		for &pid in processes.pids.iter() {
+			// Ignore failures, as the process might have exited meanwhile.
+			delivered |= Self::send(pid, signal).is_ok();
+		}
+
+		delivered
+	}
+
+
+	/// Send a signal to the process and its descendants. Returns whether the process
+	/// itself was signaled. The descendants are collected beforehand, as they are
+	/// reparented when the process exits.
+	fn send(pid: u32, signal: libc::c_int) -> std::io::Result<()> {
+		let mut tree = signal::process_tree(pid as libc::pid_t).into_iter();
+		let pid = tree.next().expect("process tree is missing its root");
+
+		let result = signal::send(pid, signal);
+
+		for descendant in tree {
+			let _ = signal::send(descendant, signal);
+		}
+
+		result
+	}
+
+
+	fn processes(&self) -> std::sync::MutexGuard<'_, Processes> {
+		// The lock is never held across operations that may panic.
+		self.0.lock().expect("job lock poisoned")
+	}
+}
+
+
+/// A handle to an asynchronous command block.
+#[derive(Debug, Finalize)]
+struct Handle {
+	thread: RefCell<Option<Thread>>,
+	job: Arc<Job>,
+}
+
+
+unsafe impl Trace for Handle {
+	gc::unsafe_empty_trace!();
+}
+
+
+impl Handle {
+	/// Whether the block is still executing.
+	fn is_running(&self) -> bool {
+		self.thread
+			.borrow()
+			.as_ref()
+			.map(|thread| !thread.is_finished())
+			.unwrap_or(false)
+	}
+
+
+	/// Wait until the block finishes, or until the deadline is reached.
+	/// Returns whether the block has finished.
+	fn wait(&self, deadline: Option<Instant>) -> bool {
+		loop {
+			if !self.is_running() {
+				return true;
+			}
+
+			let now = Instant::now();
+			let sleep = match deadline {
+				Some(deadline) if now >= deadline => return false,
+				Some(deadline) => POLL_INTERVAL.min(deadline - now),
+				None => POLL_INTERVAL,
+			};
+
+			thread::sleep(sleep);
+		}
+	}
+}
+
+
+/// The running asynchronous command blocks.
+#[derive(Debug, Default)]
+pub struct Jobs(Vec<(Value, Gc<Handle>)>);
+
+
+impl Jobs {
+	/// Register the thread executing a command block, producing the value of the block.
+	pub fn spawn(&mut self, thread: Thread, job: Arc<Job>) -> Value {
+		self.prune();
+
+		thread_local! {
+			pub static JOIN: Value = "join".into();
+			pub static PIDS: Value = "pids".into();
+			pub static IS_RUNNING: Value = "is_running".into();
+			pub static KILL: Value = "kill".into();
+			pub static WAIT: Value = "wait".into();
+		}
+
+		let handle = Gc::new(
+			Handle {
+				thread: RefCell::new(Some(thread)),
+				job,
+			}
+		);
+
+		let mut dict = HashMap::new();
+
+		JOIN.with(
+			|join| dict.insert(join.copy(), Join(handle.clone()).into())
+		);
+		PIDS.with(
+			|pids| dict.insert(pids.copy(), Pids(handle.clone()).into())
+		);
+		IS_RUNNING.with(
+			|is_running| dict.insert(is_running.copy(), IsRunning(handle.clone()).into())
+		);
+		KILL.with(
+			|kill| dict.insert(kill.copy(), Kill(handle.clone()).into())
+		);
+		WAIT.with(
+			|wait| dict.insert(wait.copy(), Wait(handle.clone()).into())
+		);
+
+		let value: Value = Dict::new(dict).into();
+
+		self.0.push((value.copy(), handle));
+
+		value
+	}
+
+
+	/// List the blocks which are still running.
+	pub fn list(&mut self) -> Value {
+		self.prune();
+
+		self.0
+			.iter()
+			.map(|(value, _)| value.copy())
+			.collect::<Vec<Value>>()
+			.into()
+	}
+
+
+	/// Drop the blocks which have finished or have been joined. Their handles are kept by
+	/// the script, if it still needs them.
+	fn prune(&mut self) {
+		self.0.retain(|(_, handle)| handle.is_running());
+	}
+}
+
+
+#[derive(Trace, Finalize)]
+struct Join(Gc<Handle>);
+
+
+impl NativeFun for Join {
+	fn name(&self) -> &'static str { "<command>.join" }
+
+	fn call(&self, context: CallContext) -> Result<Value, RuntimePanic> {
+		let thread = self.0.thread.borrow_mut().take();
+
+		match thread {
+			Some(thread) => {
+				let result = match thread.join() {
+					Ok(result) => result,
+					Err(error) => std::panic::resume_unwind(error),
+				};
+
+				result
+					.map(|errors| errors.into_value(context.interner()))
+					.map_err(Into::into)
+			},
+
+			None => Err(
+				RuntimePanic::invalid_join(context.pos),
+			)
+		}
+	}
+}
+
+
+/// The pids of the running processes. This is a function rather than a field, as the
+/// processes change while the block executes, and none may have been spawned yet when
+/// the handle is produced.
+#[derive(Trace, Finalize)]
+struct Pids(Gc<Handle>);
+
+
+impl NativeFun for Pids {
+	fn name(&self) -> &'static str { "<command>.pids" }
+
+	fn call(&self, context: CallContext) -> Result<Value, RuntimePanic> {
+		let args = context.args();
+		if !args.is_empty() {
+			return Err(RuntimePanic::invalid_args(args.len() as u32, 0, context.pos));
+		}
+
+		Ok(
+			self.0.job
+				.pids()
+				.into_iter()
+				.map(|pid| Value::Int(pid.into()))
+				.collect::<Vec<Value>>()
+				.into()
+		)
+	}
+}
+
+
+#[derive(Trace, Finalize)]
+struct IsRunning(Gc<Handle>);
+
+
+impl NativeFun for IsRunning {
+	fn name(&self) -> &'static str { "<command>.is_running" }
+
+	fn call(&self, context: CallContext) -> Result<Value, RuntimePanic> {
+		let args = context.args();
+		if !args.is_empty() {
+			return Err(RuntimePanic::invalid_args(args.len() as u32, 0, context.pos));
+		}
+
+		Ok(self.0.is_running().into())
+	}
+}
+
+
+#[derive(Trace, Finalize)]
+struct Kill(Gc<Handle>);
+
+
+impl NativeFun for Kill {
+	fn name(&self) -> &'static str { "<command>.kill" }
+
+	fn call(&self, context: CallContext) -> Result<Value, RuntimePanic> {
+		let signal = match context.args() {
+			[] => libc::SIGTERM,
+			[ value ] => signal::number(value).ok_or_else(
+				|| RuntimePanic::value_error(value.copy(), "signal", context.pos.copy())
+			)?,
+			args => return Err(RuntimePanic::invalid_args(args.len() as u32, 1, context.pos))
+		};
+
+		let delivered = self.0.is_running() && self.0.job.kill(signal);
+
+		Ok(delivered.into())
+	}
+}
+
+
+#[derive(Trace, Finalize)]
+struct Wait(Gc<Handle>);
+
+
+impl NativeFun for Wait {
+	fn name(&self) -> &'static str { "<command>.wait" }
+
+	fn call(&self, context: CallContext) -> Result<Value, RuntimePanic> {
+		let timeout = match context.args() {
+			[] | [ Value::Nil ] => None,
+			[ Value::Int(i) ] if *i >= 0 => Some(Duration::from_secs(*i as u64)),
+			[ Value::Float(f) ] if f.0 >= 0.0 && f.0.is_finite() => Some(Duration::from_secs_f64(f.0)),
+
+			[ value @ Value::Int(_) ] | [ value @ Value::Float(_) ] => return Err(
+				RuntimePanic::value_error(value.copy(), "positive number", context.pos)
+			),
+			[ other ] => return Err(
+				RuntimePanic::type_error(other.copy(), "int or float", context.pos)
+			),
+			args => return Err(RuntimePanic::invalid_args(args.len() as u32, 1, context.pos))
+		};
+
+		let deadline = timeout.and_then(|timeout| Instant::now().checked_add(timeout));
+
+		Ok(self.0.wait(deadline).into())
+	}
+}
+
@@ -1,18 +1,26 @@
+mod alias;
 mod error;
 mod fmt;
-mod join;
+mod job;
+mod stream;
 
 use std::{
+	collections::BTreeMap,
 	ffi::{OsStr, OsString},
 	fs::{File, OpenOptions},
 	io::{self, Write},
-	os::unix::prelude::{FromRawFd, OsStrExt, ExitStatusExt, IntoRawFd},
+	os::unix::prelude::{AsRawFd, CommandExt, FromRawFd, OsStrExt, ExitStatusExt, IntoRawFd, RawFd},
 	process,
+	sync::Arc,
+	thread,
+	time::{Duration, Instant},
 };
 
-use crate::io::FileDescriptor;
-use super::{program, SourcePos};
-pub use join::Join;
+use crate::{io::FileDescriptor, runtime::signal};
+use super::SourcePos;
+pub use alias::Aliases;
+pub use job::{Job, Jobs};
+pub use stream::Stream;
 pub use error::{Panic, Error, PipelineErrors, IntoValue};
 
 
@@ -20,6 +28,12 @@ pub use error::{Panic, Error, PipelineErrors, IntoValue};
 const IO_ERROR_STATUS: i32 = 0x7F;
 /// Offset of a signal status, according to Bash and Dash.
 const SIGNAL_STATUS_OFFSET: i32 = 0xFF;
+/// Status to be produced when a command times out, according to coreutils' timeout.
+const TIMEOUT_STATUS: i32 = 124;
+/// How long to wait for commands to exit after SIGTERM, before sending SIGKILL.
+const TERM_GRACE_PERIOD: Duration = Duration::from_secs(2);
+/// Interval between checks of running commands when there is a deadline.
+const POLL_INTERVAL: Duration = Duration::from_millis(10);
 
 
 /// Execution status of a single command.
@@ -27,11 +41,25 @@ const SIGNAL_STATUS_OFFSET: i32 = 0xFF;
 pub struct ErrorStatus {
 	description: String,
 	status: i32,
+	/// Whether the command was killed due to a timeout.
+	timeout: bool,
 	pos: SourcePos,
 }
 
 
 impl ErrorStatus {
+	/// The status of a command which exceeded its deadline.
+	fn timeout(pos: SourcePos) -> Self {
+		Self {
+			description: "command timed out".into(),
+			status: TIMEOUT_STATUS,
+			timeout: true,
+			pos,
+		}
+	}
+
+
+
 	/// Wait a child process, and return the status.
 	fn wait_child(mut child: Child) -> Option<Self> {
 		let status = match child.process.wait() {
@@ -40,6 +68,7 @@ impl ErrorStatus {
 				Self {
 					description: error.to_string(),
 					status: IO_ERROR_STATUS,
+					timeout: false,
 					pos: child.pos,
 				}
 			)
@@ -63,6 +92,7 @@ impl ErrorStatus {
 				Self {
 					description: "command returned non-zero".into(),
 					status: code,
+					timeout: false,
 					pos: child.pos,
 				}
 			)
@@ -126,6 +156,8 @@ pub enum RedirectionTarget {
 	Overwrite(Argument),
 	/// Append to a file. Panics if the argument does not expand to a single literal.
 	Append(Argument),
+	/// Close the file descriptor.
+	Close,
 }
 
 
@@ -139,6 +171,8 @@ pub enum Redirection {
 	},
 	/// Redirect input from a file or literal.
 	Input {
+		/// The file descriptor to be redirected.
+		target: FileDescriptor,
 		/// Whether the source is the input or the file path.
 		literal: bool,
 		/// The source argument. Panics if the argument does not expand to a single literal.
@@ -148,10 +182,11 @@ pub enum Redirection {
 
 
 /// Built-in commands.
-#[derive(Debug, Copy, Clone)]
+#[derive(Debug)]
 pub enum Builtin {
-	Alias,
+	Alias(Aliases),
 	Cd,
+	Unalias(Aliases),
 }
 
 
@@ -159,12 +194,60 @@ impl Builtin {
 	pub fn exec(
 		self,
 		arguments: Box<[Argument]>,
+		mut stdout: os_pipe::PipeWriter,
 		pos: SourcePos,
 	) -> Result<Option<ErrorStatus>, Error> {
 		let mut arguments = arguments.into_vec();
 
 		match self {
-			Builtin::Alias => todo!(),
+			Builtin::Alias(aliases) => {
+				let mut args = Vec::new();
+				for arg in arguments {
+					args.extend(arg.resolve(pos.copy())?.into_vec());
+				}
+
+				let mut print = |name: &OsStr, alias: &[Box<OsStr>]| {
+					let mut line = b"alias ".to_vec();
+					line.extend_from_slice(name.as_bytes());
+
+					for word in alias {
+						line.push(b' ');
+						line.extend_from_slice(word.as_bytes());
+					}
+
+					line.push(b'\n');
+
+					stdout
+						.write_all(&line)
+						.map_err(|error| Error::io(error, pos.copy()))
+				};
+
+				match args.as_slice() {
+					[] => {
+						for (name, alias) in aliases.list() {
+							print(&name, &alias)?;
+						}
+					}
+
+					[ name ] => match aliases.get(name) {
+						Some(alias) => print(name, &alias)?,
+						None => return Ok(
+							Some(
+								ErrorStatus {
+									description: "alias not found".into(),
+									status: 1,
+									timeout: false,
+									pos,
+								}
+							)
+						),
+					},
+
+					[ name, alias @ .. ] => aliases.insert(name.clone(), alias.into()),
+				}
+
+				Ok(None)
+			}
 
 			Builtin::Cd => {
 				let arg = arguments
@@ -189,16 +272,34 @@ impl Builtin {
 
 				Ok(None)
 			}
-		}
-	}
-}
 
+			Builtin::Unalias(aliases) => {
+				if arguments.is_empty() {
+					return Err(Panic::invalid_args("argument", 0, pos).into());
+				}
+
+				let mut missing = false;
+				for arg in arguments {
+					for name in arg.resolve(pos.copy())?.iter() {
+						missing |= !aliases.remove(name);
+					}
+				}
 
-impl<'a> From<&'a program::command::Builtin> for Builtin {
-	fn from(builtin: &'a program::command::Builtin) -> Self {
-		match builtin {
-			program::command::Builtin::Alias => Self::Alias,
-			program::command::Builtin::Cd => Self::Cd,
+				if missing {
+					Ok(
+						Some(
+							ErrorStatus {
+								description: "alias not found".into(),
+								status: 1,
+								timeout: false,
+								pos,
+							}
+						)
+					)
+				} else {
+					Ok(None)
+				}
+			}
 		}
 	}
 }
@@ -221,6 +322,8 @@ pub struct BasicCommand {
 	pub env: Box<[(Box<OsStr>, Argument)]>,
 	/// Arguments to the program. The arguments may expand to an arbitrary number of literals.
 	pub arguments: Box<[Argument]>,
+	/// Working directory of the program. Defaults to the current directory.
+	pub cwd: Option<Box<OsStr>>,
 	/// Redirections to be placed in order.
 	pub redirections: Box<[Redirection]>,
 	/// Whether to abort the command block execution if the command fails.
@@ -262,31 +365,112 @@ impl BasicCommand {
 			}
 		}
 
+		if let Some(cwd) = self.cwd {
+			command.current_dir(cwd.as_ref());
+		}
+
 		Self::spawn(&mut command, stdio, self.redirections, self.pos)
 	}
 
 
+	/// Execute the command on its own, outside of a command block. If input is given, it
+	/// is written to the command's stdin on a dedicated thread, so that commands which
+	/// produce output before consuming all their input don't deadlock. Otherwise, stdin
+	/// is inherited.
+	pub fn exec_standalone(
+		self,
+		input: Option<Box<[u8]>>,
+		stdout: os_pipe::PipeWriter,
+		stderr: os_pipe::PipeWriter,
+		deadline: Option<Instant>,
+	) -> Result<Box<[PipelineErrors]>, Panic> {
+		let pos = self.pos.copy();
+
+		let result = (move || {
+			let (stdin, writer) = match input {
+				Some(input) => {
+					let (reader, writer) = os_pipe::pipe()
+						.map_err(|error| Error::io(error, pos.copy()))?;
+					(reader, Some((writer, input)))
+				}
+
+				None => {
+					let stdin = os_pipe::dup_stdin()
+						.map_err(|error| Error::io(error, pos.copy()))?;
+					(stdin, None)
+				}
+			};
+
+			let child = self.exec(Stdio { stdin, stdout, stderr })?;
+
+			// The writer is dropped when the thread finishes, signaling EOF to the command.
+			let writer = writer.map(
+				|(mut writer, input)| thread::spawn(
+					move || writer.write_all(&input)
+				)
+			);
+
+			let mut children = [(child, false)];
+
+			let timeout = deadline
+				.map(|deadline| Child::enforce_deadline(&mut children, deadline))
+				.unwrap_or(false);
+
+			let [(child, _)] = children;
+			let error = ErrorStatus::wait_child(child);
+
+			if let Some(writer) = writer {
+				match writer.join() {
+					Err(error) => std::panic::resume_unwind(error),
+					// The command is not required to consume all of its input.
+					Ok(Err(error)) if error.kind() != io::ErrorKind::BrokenPipe => {
+						return Err(Error::io(error, pos));
+					}
+					Ok(_) => (),
+				}
+			}
+
+			let errors = PipelineErrors::from(
+				if timeout {
+					Some(ErrorStatus::timeout(pos))
+				} else {
+					error
+				}
+			);
+
+			Ok(
+				if errors.is_empty() {
+					Box::default()
+				} else {
+					Box::new([errors]) as Box<[PipelineErrors]>
+				}
+			)
+		})();
+
+		Self::into_status(result)
+	}
+
+
 	fn spawn(
 		command: &mut process::Command,
-		mut stdio: Stdio,
+		stdio: Stdio,
 		redirections: Box<[Redirection]>,
 		pos: SourcePos,
 	) -> Result<Child, Error> {
+		// The file descriptors to be set in the child process. None means closed.
+		let mut fds: BTreeMap<FileDescriptor, Option<File>> = BTreeMap::new();
+		fds.insert(0, Some(Self::file_from_raw_fd(stdio.stdin.into_raw_fd())));
+		fds.insert(1, Some(Self::file_from_raw_fd(stdio.stdout.into_raw_fd())));
+		fds.insert(2, Some(Self::file_from_raw_fd(stdio.stderr.into_raw_fd())));
+
 		for redirection in redirections.into_vec() { // Use vec's owned iterator.
 			match redirection {
 				Redirection::Output { source, target } => {
-					let target = Self::resolve_target(target, &stdio, pos.copy())?;
-
-					match source {
-						1 => stdio.stdout = target,
-						2 => stdio.stderr = target,
-						other => return Err(
-							Panic::unsupported_fd(other, pos.copy()).into()
-						),
-					};
+					let target = Self::resolve_target(target, &fds, pos.copy())?;
+					fds.insert(source, target);
 				}
 
-				Redirection::Input { literal, source } => {
+				Redirection::Input { target, literal, source } => {
 					let args = source.resolve(pos.copy())?;
 
 					let source = match args.as_ref() {
@@ -296,7 +480,7 @@ impl BasicCommand {
 						),
 					};
 
-					let stdin: os_pipe::PipeReader =
+					let file =
 						if literal {
 							let (reader, mut writer) = os_pipe::pipe()
 								.map_err(|error| Error::io(error, pos.copy()))?;
@@ -307,25 +491,47 @@ impl BasicCommand {
 							writer.write_all(b"\n")
 								.map_err(|error| Error::io(error, pos.copy()))?;
 
-							reader
+							Self::file_from_raw_fd(reader.into_raw_fd())
 						} else {
-							let file = File::open(source.as_ref())
+							File::open(source.as_ref())
 								.map_err(|error| Error::io(error, pos.copy()))?
-								.into_raw_fd();
-
-							// SAFETY: converting from a FD originated from a File is fine.
-							unsafe { os_pipe::PipeReader::from_raw_fd(file) }
 						};
 
-					stdio.stdin = stdin;
+					fds.insert(target, Some(file));
 				}
 			}
 		}
 
-		command.stdin(stdio.stdin);
-		command.stdout(stdio.stdout);
-		command.stderr(stdio.stderr);
+		// Standard streams are set through the process builder. Closed ones are set to
+		// null, and then closed along with the other redirections.
+		let mut std_stream = |fd| match fds.remove(&fd) {
+			Some(Some(file)) => process::Stdio::from(file),
+			_ => {
+				fds.insert(fd, None);
+				process::Stdio::null()
+			}
+		};
+
+		command.stdin(std_stream(0));
+		command.stdout(std_stream(1));
+		command.stderr(std_stream(2));
+
+		if !fds.is_empty() {
+			let mut redirections: Vec<(FileDescriptor, Option<RawFd>)> = fds
+				.iter()
+				.map(|(fd, file)| (*fd, file.as_ref().map(File::as_raw_fd)))
+				.collect();
+
+			// SAFETY: the closure only performs async-signal-safe system calls, and
+			// doesn't allocate memory. The raw fds are kept alive by `fds` until the
+			// process is spawned.
+			unsafe { command.pre_exec(move || Self::redirect_fds(&mut redirections)) };
+		}
 
+		// Signals caught through std.signal.on are reset to their default action by exec,
+		// while ignored signals are inherited, just like in POSIX shells. SIGPIPE, which
+		// the Rust runtime ignores in Hush itself, is reset to the default action in the
+		// child by the standard library.
 		let process = command.spawn()
 			.map_err(|error| Error::io(error, pos.copy()))?;
 
@@ -333,48 +539,132 @@ impl BasicCommand {
 	}
 
 
-	fn resolve_target(target: RedirectionTarget, stdio: &Stdio, pos: SourcePos) -> Result<os_pipe::PipeWriter, Error> {
+	/// Place the given file descriptors in the child process, after fork and before exec.
+	/// Each item is the target fd and its source, where None means the target should
+	/// be closed.
+	fn redirect_fds(redirections: &mut [(FileDescriptor, Option<RawFd>)]) -> io::Result<()> {
+		let max_target = redirections
+			.iter()
+			.map(|(target, _)| *target)
+			.max()
+			.unwrap_or(0);
+
+		// Move all sources above the greatest target, so that no source is overwritten
+		// before being duplicated.
+		for (_, source) in redirections.iter_mut() {
+			if let Some(fd) = source {
+				let new_fd = unsafe { libc::fcntl(*fd, libc::F_DUPFD, max_target + 1) };
+				if new_fd < 0 {
+					return Err(io::Error::last_os_error());
+				}
+
+				*fd = new_fd;
+			}
+		}
+
+		for (target, source) in redirections.iter() {
+			match source {
+				Some(fd) => if unsafe { libc::dup2(*fd, *target) } < 0 {
+					return Err(io::Error::last_os_error());
+				}
+
+				// Closing a file descriptor which is not open is not an error.
+				None => unsafe { libc::close(*target); },
+			}
+		}
+
+		for (_, source) in redirections.iter() {
+			if let Some(fd) = source {
+				unsafe { libc::close(*fd) };
+			}
+		}
+
+		Ok(())
+	}
+
+
+	/// Resolve the target of a output redirection or fd duplication.
+	/// None means the source fd should be closed.
+	fn resolve_target(
+		target: RedirectionTarget,
+		fds: &BTreeMap<FileDescriptor, Option<File>>,
+		pos: SourcePos,
+	) -> Result<Option<File>, Error> {
 		let open = |arg: Argument, append| {
 			let args = arg.resolve(pos.copy())?;
 
-			let file = match args.as_ref() {
+			match args.as_ref() {
 				[ file ] => OpenOptions::new()
 					.create(true)
 					.write(true)
 					.append(append)
 					.truncate(!append)
 					.open(file.as_ref())
-					.map_err(|error| Error::io(error, pos.copy()))?
-					.into_raw_fd(),
+					.map(Some)
+					.map_err(|error| Error::io(error, pos.copy())),
 
-				other => return Err(
+				other => Err(
 					Panic::invalid_args("redirection", other.len() as u32, pos.copy()).into()
 				),
-			};
-
-			Ok(
-				// SAFETY: converting from a FD originated from a File is fine.
-				unsafe { os_pipe::PipeWriter::from_raw_fd(file) }
-			)
+			}
 		};
 
 		match target {
 			RedirectionTarget::Overwrite(arg) => open(arg, false),
 			RedirectionTarget::Append(arg) => open(arg, true),
+			RedirectionTarget::Close => Ok(None),
 			RedirectionTarget::Fd(fd) => {
-				let writer = match fd {
-					1 => &stdio.stdout,
-					2 => &stdio.stderr,
-					other => return Err(
-						Panic::unsupported_fd(other, pos.copy()).into()
-					),
+				let file = match fds.get(&fd) {
+					Some(Some(file)) => file.try_clone(),
+
+					// The fd has been closed by a previous redirection.
+					Some(None) => Err(io::Error::from_raw_os_error(libc::EBADF)),
+
+					// The fd is not redirected, so duplicate the one inherited by Hush.
+					None => {
+						let new_fd = unsafe { libc::fcntl(fd, libc::F_DUPFD_CLOEXEC, 0) };
+						if new_fd < 0 {
+							Err(io::Error::last_os_error())
+						} else {
+							Ok(Self::file_from_raw_fd(new_fd))
+						}
+					}
 				};
 
-				writer.try_clone()
+				file
+					.map(Some)
 					.map_err(|error| Error::io(error, pos))
 			}
 		}
 	}
+
+
+	/// Convert the result of an execution to its status. IO errors are reported as
+	/// command errors, instead of panics.
+	fn into_status(
+		result: Result<Box<[PipelineErrors]>, Error>
+	) -> Result<Box<[PipelineErrors]>, Panic> {
+		match result {
+			Ok(status) => Ok(status),
+			Err(Error::Panic(panic)) => Err(panic),
+			Err(Error::Io { error, pos }) => {
+				let error = ErrorStatus {
+					description: error.to_string(),
+					status: IO_ERROR_STATUS,
+					timeout: false,
+					pos,
+				};
+
+				Ok(Box::new([PipelineErrors::from(error)]))
+			},
+		}
+	}
+
+
+	fn file_from_raw_fd(fd: RawFd) -> File {
+		// SAFETY: the fd is owned, as it originated from a File, a pipe or a dup.
+		unsafe { File::from_raw_fd(fd) }
+	}
 }
 
 
@@ -385,6 +675,68 @@ pub struct Child {
 }
 
 
+impl Child {
+	/// Wait for the given children until the deadline. If any of them is still running
+	/// after the deadline, the remaining children and their descendants are sent SIGTERM,
+	/// and then SIGKILL if they don't exit after the grace period.
+	/// Returns whether the deadline was exceeded.
+	fn enforce_deadline(children: &mut [(Child, bool)], deadline: Instant) -> bool {
+		if Self::wait_until(children, deadline) {
+			return false;
+		}
+
+		// Collect the descendants beforehand, as they are reparented when their parents
+		// exit.
+		let mut tree = Vec::new();
+
+		for (child, _) in children.iter_mut() {
+			if let Ok(None) = child.process.try_wait() {
+				tree.extend(signal::process_tree(child.process.id() as libc::pid_t));
+			}
+		}
+
+		Self::signal_tree(&tree, libc::SIGTERM);
+
+		if !Self::wait_until(children, Instant::now() + TERM_GRACE_PERIOD) {
+			Self::signal_tree(&tree, libc::SIGKILL);
+		}
+
+		true
+	}
+
+
+	/// Send a signal to the given processes. Failures are ignored, as the processes might
+	/// have exited meanwhile.
+	fn signal_tree(tree: &[libc::pid_t], signal: libc::c_int) {
+		for &pid in tree {
+			let _ = signal::send(pid, signal);
+		}
+	}
+
+
+	/// Poll the given children until all have exited, or the deadline is reached.
+	/// Returns whether all children have exited.
+	fn wait_until(children: &mut [(Child, bool)], deadline: Instant) -> bool {
+		loop {
+			let running = children
+				.iter_mut()
+				.any(|(child, _)| matches!(child.process.try_wait(), Ok(None)));
+
+			if !running {
+				return true;
+			}
+
+			let now = Instant::now();
+			if now >= deadline {
+				return false;
+			}
+
+			thread::sleep(POLL_INTERVAL.min(deadline - now));
+		}
+	}
+}
+
+
 #[derive(Debug)]
 pub struct CommandExec {
 	pub errors: PipelineErrors,
@@ -416,14 +768,18 @@ pub enum Command {
 
 impl Command {
 	/// Returns a pair of result value and whether to abort.
+	/// If a deadline is given, external commands which exceed it are killed.
+	/// If a job is given, spawned processes are registered in it while they run.
 	pub fn exec(
 		self,
 		stdout: os_pipe::PipeWriter,
 		stderr: os_pipe::PipeWriter,
+		deadline: Option<Instant>,
+		job: Option<&Job>,
 	) -> Result<CommandExec, Error> {
 		match self {
 			Command::Builtin { program, arguments, abort_on_error, pos } => {
-				let error = program.exec(arguments, pos)?;
+				let error = program.exec(arguments, stdout, pos)?;
 				let abort = abort_on_error && error.is_some();
 				Ok(
 					CommandExec {
@@ -452,6 +808,10 @@ impl Command {
 						}
 					)?;
 
+					if let Some(job) = job {
+						job.spawned(child.process.id());
+					}
+
 					last_stdout = pipe_writer;
 					last_stderr = os_pipe::dup_stderr()
 						.map_err(|error| Error::io(error, child.pos.copy()))?;
@@ -472,23 +832,45 @@ impl Command {
 					}
 				)?;
 
+				if let Some(job) = job {
+					job.spawned(head_child.process.id());
+				}
+
+				let pos = head_child.pos.copy();
+
+				let mut children = vec![(head_child, head_abort_on_error)];
+				children.extend(tail_children.into_iter().rev());
+
+				let timeout = deadline
+					.map(|deadline| Child::enforce_deadline(&mut children, deadline))
+					.unwrap_or(false);
+
 				let mut abort = false;
 				let mut errors = Vec::new();
 
-				// Wait on head command.
-				if let Some(error) = ErrorStatus::wait_child(head_child) {
-					abort |= head_abort_on_error;
-					errors.push(error);
-				}
+				// Wait on commands, in order.
+				for (child, abort_on_error) in children {
+					let pid = child.process.id();
+
+					let error = ErrorStatus::wait_child(child);
+
+					if let Some(job) = job {
+						job.reaped(pid);
+					}
 
-				// Wait on tail commands.
-				for (child, abort_on_error) in tail_children.into_iter().rev() {
-					if let Some(error) = ErrorStatus::wait_child(child) {
+					if let Some(error) = error {
 						abort |= abort_on_error;
 						errors.push(error);
 					}
 				}
 
+				// A timeout always aborts the block, and supersedes the errors from the
+				// killed commands.
+				if timeout {
+					abort = true;
+					errors = vec![ErrorStatus::timeout(pos)];
+				}
+
 				Ok(
 					CommandExec {
 						errors: errors.into(),
@@ -513,44 +895,57 @@ impl Command {
 pub struct Block {
 	pub head: Command,
 	pub tail: Box<[Command]>,
+	/// The job in which to register spawned processes, for asynchronous and stream blocks.
+	pub job: Option<Arc<Job>>,
 }
 
 
 impl Block {
-	pub fn exec<F, G>(self, stdout: F, stderr: G) -> Result<Box<[PipelineErrors]>, Panic>
+	/// Execute the block. If a deadline is given, commands which exceed it are killed,
+	/// and the block is aborted.
+	pub fn exec<F, G>(
+		self,
+		stdout: F,
+		stderr: G,
+		deadline: Option<Instant>,
+	) -> Result<Box<[PipelineErrors]>, Panic>
 	where
 		F: FnMut() -> io::Result<os_pipe::PipeWriter>,
 		G: FnMut() -> io::Result<os_pipe::PipeWriter>,
 	{
-		match self._exec(stdout, stderr) {
-			Ok(status) => Ok(status),
-			Err(Error::Panic(panic)) => Err(panic),
-			Err(Error::Io { error, pos }) => {
-				let error = ErrorStatus {
-					description: error.to_string(),
-					status: IO_ERROR_STATUS,
-					pos,
-				};
-
-				Ok(Box::new([PipelineErrors::from(error)]))
-			},
-		}
+		BasicCommand::into_status(self._exec(stdout, stderr, deadline))
 	}
 
 
-	fn _exec<F, G>(self, mut stdout: F, mut stderr: G,) -> Result<Box<[PipelineErrors]>, Error>
+	fn _exec<F, G>(
+		self,
+		mut stdout: F,
+		mut stderr: G,
+		deadline: Option<Instant>,
+	) -> Result<Box<[PipelineErrors]>, Error>
 	where
 		F: FnMut() -> io::Result<os_pipe::PipeWriter>,
 		G: FnMut() -> io::Result<os_pipe::PipeWriter>,
 	{
 		let mut errors = Vec::new();
 
+		let job = self.job.as_deref();
+		let last = self.tail.len();
+
+		if last == 0 {
+			if let Some(job) = job {
+				job.last_command();
+			}
+		}
+
 		let pos = self.head.pos();
 		let head = self.head.exec(
 			stdout()
 				.map_err(|error| Error::io(error, pos.copy()))?,
 			stderr()
 				.map_err(|error| Error::io(error, pos.copy()))?,
+			deadline,
+			job,
 		)?;
 
 		if !head.errors.is_empty() {
@@ -561,13 +956,22 @@ impl Block {
 			return Ok(errors.into())
 		}
 
-		for command in self.tail.into_vec() { // Use vec's owned iterator.
+		// Use vec's owned iterator.
+		for (ix, command) in self.tail.into_vec().into_iter().enumerate() {
+			if ix + 1 == last {
+				if let Some(job) = job {
+					job.last_command();
+				}
+			}
+
 			let pos = command.pos();
 			let child = command.exec(
 				stdout()
 					.map_err(|error| Error::io(error, pos.copy()))?,
 				stderr()
 					.map_err(|error| Error::io(error, pos.copy()))?,
+				deadline,
+				job,
 			)?;
 
 			if !child.errors.is_empty() {
@@ -0,0 +1,152 @@
+use std::{
+	collections::HashMap,
+	io::{BufRead, BufReader},
+	sync::Arc,
+};
+
+use gc::{Finalize, GcCell, Trace};
+
+use crate::runtime::{
+	value::{keys, CallContext, Dict, NativeFun, Value},
+	Panic,
+};
+
+use super::{error, Job, PipelineErrors, IntoValue};
+
+
+/// The running command block, and the read end of its stdout.
+#[derive(Finalize)]
+struct Producer {
+	lines: BufReader<os_pipe::PipeReader>,
+	handle: std::thread::JoinHandle<Result<Box<[PipelineErrors]>, error::Panic>>,
+	job: Arc<Job>,
+}
+
+
+unsafe impl Trace for Producer {
+	gc::unsafe_empty_trace!();
+}
+
+
+/// An iterator over the stdout lines of a command block.
+/// Once the output is drained, the iterator finishes with the block's error, if any.
+/// If a for loop stops iterating the stream before that, or if the stream is collected,
+/// the block is terminated.
+#[derive(Trace)]
+pub struct Stream {
+	producer: GcCell<Option<Producer>>,
+	status: GcCell<Value>,
+}
+
+
+impl Stream {
+	pub fn new(
+		lines: os_pipe::PipeReader,
+		handle: std::thread::JoinHandle<Result<Box<[PipelineErrors]>, error::Panic>>,
+		job: Arc<Job>,
+	) -> Self {
+		Self {
+			producer: GcCell::new(
+				Some(
+					Producer {
+						lines: BufReader::new(lines),
+						handle,
+						job,
+					}
+				)
+			),
+			status: GcCell::new(Value::default()),
+		}
+	}
+
+
+	/// Terminate the block, if it is still running. Closing the pipe stops writers, and
+	/// the signal stops the remaining commands. The thread is not joined, as the commands
+	/// might ignore the signal.
+	fn terminate(&self) {
+		if let Some(producer) = self.producer.borrow_mut().take() {
+			drop(producer.lines);
+
+			if !producer.handle.is_finished() {
+				producer.job.kill(libc::SIGTERM);
+			}
+		}
+	}
+}
+
+
+impl Finalize for Stream {
+	fn finalize(&self) {
+		self.terminate();
+	}
+}
+
+
+impl NativeFun for Stream {
+	fn name(&self) -> &'static str { "<command>.stream" }
+
+	fn close(&self) {
+		self.terminate();
+	}
+
+	fn call(&self, context: CallContext) -> Result<Value, Panic> {
+		thread_local! {
+			pub static ERROR: Value = "error".into();
+		}
+
+		let args = context.args();
+		if !args.is_empty() {
+			return Err(Panic::invalid_args(args.len() as u32, 0, context.pos));
+		}
+
+		let mut iteration = HashMap::new();
+		let mut producer = self.producer.borrow_mut();
+
+		if let Some(Producer { lines, .. }) = producer.as_mut() {
+			let mut line = Vec::new();
+
+			let read = lines
+				.read_until(b'\n', &mut line)
+				.map_err(|error| Panic::io(error, context.pos.copy()))?;
+
+			if read != 0 {
+				if line.last() == Some(&b'\n') {
+					line.pop();
+				}
+
+				keys::FINISHED.with(
+					|finished| iteration.insert(finished.copy(), false.into())
+				);
+				keys::VALUE.with(
+					|value| iteration.insert(value.copy(), line.into_boxed_slice().into())
+				);
+
+				return Ok(Dict::new(iteration).into());
+			}
+
+			// The output has been drained, which means the block is done writing. Dropping
+			// the reader before joining ensures we don't hold the pipe open.
+			let Producer { handle, .. } = producer
+				.take()
+				.expect("producer should be present");
+
+			let result = match handle.join() {
+				Ok(result) => result,
+				Err(error) => std::panic::resume_unwind(error),
+			};
+
+			*self.status.borrow_mut() = result
+				.map(|errors| errors.into_value(context.interner()))
+				.map_err(Panic::from)?;
+		}
+
+		keys::FINISHED.with(
+			|finished| iteration.insert(finished.copy(), true.into())
+		);
+		ERROR.with(
+			|error| iteration.insert(error.copy(), self.status.borrow().copy())
+		);
+
+		Ok(Dict::new(iteration).into())
+	}
+}
@@ -6,7 +6,7 @@ use std::{
 	collections::HashMap,
 	os::unix::{ffi::OsStrExt, prelude::OsStringExt},
 	path::PathBuf,
-	ops::DerefMut, io::Read, ffi::{OsStr, OsString}, thread
+	ops::DerefMut, io::Read, ffi::{OsStr, OsString}, sync::Arc, thread
 };
 
 use super::{
@@ -18,7 +18,19 @@ use super::{
 	Value,
 };
 use arg::Args;
-use exec::IntoValue;
+use exec::{IntoValue, PipelineErrors};
+pub use exec::{Aliases, Jobs};
+
+
+/// A program to be executed outside of a command block, as in `std.exec`.
+pub struct ExecOptions {
+	pub program: Box<OsStr>,
+	pub args: Box<[Box<OsStr>]>,
+	pub env: Box<[(Box<OsStr>, Box<OsStr>)]>,
+	pub cwd: Option<Box<OsStr>>,
+	/// Data to be written to the program's stdin. If none, stdin is inherited.
+	pub stdin: Option<Box<[u8]>>,
+}
 
 
 impl Runtime {
@@ -35,113 +47,177 @@ impl Runtime {
 					.exec(
 						os_pipe::dup_stdout,
 						os_pipe::dup_stderr,
+						self.deadline,
 					)
 					.map(|errors| errors.into_value(self.interner()))
 					.map_err(Into::into)
 			}
 
 			program::CommandBlockKind::Capture => {
-				thread_local! {
-					pub static ERROR: Value = "error".into();
-					pub static STDOUT: Value = "stdout".into();
-					pub static STDERR: Value = "stderr".into();
-				}
+				let deadline = self.deadline;
+
+				self.capture(
+					// We must drop all writers before attempting to read, otherwise we'll deadlock.
+					move |stdout, stderr| command_block.exec(
+						move || stdout.try_clone(),
+						move || stderr.try_clone(),
+						deadline,
+					),
+					pos,
+				)
+			}
 
-				let (mut stdout_read, stdout_write) = os_pipe::pipe()
-					.map_err(|error| Panic::io(error, pos.copy()))?;
+			program::CommandBlockKind::Asynchronous => {
+				let job = Arc::new(exec::Job::default());
+				let mut command_block = command_block;
+				command_block.job = Some(job.clone());
 
-				let (mut stderr_read, stderr_write) = os_pipe::pipe()
-					.map_err(|error| Panic::io(error, pos.copy()))?;
+				let deadline = self.deadline;
+				let join_handle = std::thread::spawn(
+					move || command_block.exec(
+						os_pipe::dup_stdout,
+						os_pipe::dup_stderr,
+						deadline,
+					)
+				);
 
-				let stdout_reader = thread::spawn(move || {
-					let mut data = Vec::with_capacity(512);
-					stdout_read.read_to_end(&mut data)?;
-					Ok(data)
-				});
+				Ok(self.jobs.spawn(join_handle, job))
+			}
+
+			program::CommandBlockKind::Stream => {
+				let (stdout_read, stdout_write) = os_pipe::pipe()
+					.map_err(|error| Panic::io(error, pos.copy()))?;
 
-				let stderr_reader = thread::spawn(move || {
-					let mut data = Vec::with_capacity(512);
-					stderr_read.read_to_end(&mut data)?;
-					Ok(data)
-				});
+				let job = Arc::new(exec::Job::default());
+				let mut command_block = command_block;
+				command_block.job = Some(job.clone());
 
-				let errors = command_block
-					.exec(
-						// We must drop all writers before attempting to read, otherwise we'll deadlock.
+				let deadline = self.deadline;
+				let join_handle = std::thread::spawn(
+					// The writer is dropped once the block finishes, which signals EOF to the reader.
+					move || command_block.exec(
 						move || stdout_write.try_clone(),
-						move || stderr_write.try_clone(),
+						os_pipe::dup_stderr,
+						deadline,
 					)
-					.map_err(Panic::from)?;
-
-				let mut result = errors.into_value(self.interner());
-				let mut captures = {
-					let out = match stdout_reader.join() {
-						Err(error) => std::panic::resume_unwind(error),
-						Ok(result) => result
-							.map_err(|error| Panic::io(error, pos.copy()))?
-							.into_boxed_slice(),
-					};
-
-					let err = match stderr_reader.join() {
-						Err(error) => std::panic::resume_unwind(error),
-						Ok(result) => result
-							.map_err(|error| Panic::io(error, pos.copy()))?
-							.into_boxed_slice(),
-					};
-
-					let mut dict = HashMap::new();
-
-					STDOUT.with(
-						|stdout| dict.insert(stdout.copy(), out.into())
-					);
-					STDERR.with(
-						|stderr| dict.insert(stderr.copy(), err.into())
-					);
-
-					dict
-				};
+				);
 
-				match &mut result {
-					Value::Nil => Ok(Dict::new(captures).into()),
-					Value::Error(error) => {
-						let ctx = std::mem::take(error.context.borrow_mut().deref_mut());
+				Ok(exec::Stream::new(stdout_read, join_handle, job).into())
+			}
+		}
+	}
 
-						ERROR.with(
-							|error| captures.insert(error.copy(), ctx)
-						);
 
-						*error.context.borrow_mut() = Dict::new(captures).into();
+	/// Execute a single program, capturing its output like a capture block.
+	pub(super) fn exec_program(
+		&mut self,
+		options: ExecOptions,
+		pos: SourcePos,
+	) -> Result<Value, Panic> {
+		let command = exec::BasicCommand {
+			program: exec::Argument::Literal(options.program),
+			env: options.env
+				.into_vec() // Use vec's owned iterator.
+				.into_iter()
+				.map(|(key, value)| (key, exec::Argument::Literal(value)))
+				.collect(),
+			arguments: options.args
+				.into_vec() // Use vec's owned iterator.
+				.into_iter()
+				.map(exec::Argument::Literal)
+				.collect(),
+			cwd: options.cwd,
+			redirections: Default::default(),
+			abort_on_error: false,
+			pos: pos.copy(),
+		};
 
-						Ok(result)
-					},
-					_ => unreachable!("exec should only produce nil or error"),
-				}
-			}
+		let deadline = self.deadline;
+		let stdin = options.stdin;
 
-			program::CommandBlockKind::Asynchronous => {
-				thread_local! {
-					pub static JOIN: Value = "join".into();
-				}
+		self.capture(
+			move |stdout, stderr| command.exec_standalone(stdin, stdout, stderr, deadline),
+			pos,
+		)
+	}
 
-				let join_handle = std::thread::spawn(
-					|| command_block.exec(
-						os_pipe::dup_stdout,
-						os_pipe::dup_stderr,
-					)
-				);
 
-				let join_handle = exec::Join
-					::new(join_handle)
-					.into();
+	/// Run the given execution, capturing stdout and stderr. The result is a dict with
+	/// the captured outputs, or an error whose context is such dict with an additional
+	/// error field.
+	fn capture<F>(&mut self, exec: F, pos: SourcePos) -> Result<Value, Panic>
+	where
+		F: FnOnce(os_pipe::PipeWriter, os_pipe::PipeWriter) -> Result<Box<[PipelineErrors]>, exec::Panic>,
+	{
+		thread_local! {
+			pub static ERROR: Value = "error".into();
+			pub static STDOUT: Value = "stdout".into();
+			pub static STDERR: Value = "stderr".into();
+		}
+
+		let (mut stdout_read, stdout_write) = os_pipe::pipe()
+			.map_err(|error| Panic::io(error, pos.copy()))?;
+
+		let (mut stderr_read, stderr_write) = os_pipe::pipe()
+			.map_err(|error| Panic::io(error, pos.copy()))?;
+
+		let stdout_reader = thread::spawn(move || {
+			let mut data = Vec::with_capacity(512);
+			stdout_read.read_to_end(&mut data)?;
+			Ok(data)
+		});
+
+		let stderr_reader = thread::spawn(move || {
+			let mut data = Vec::with_capacity(512);
+			stderr_read.read_to_end(&mut data)?;
+			Ok(data)
+		});
+
+		let errors = exec(stdout_write, stderr_write)
+			.map_err(Panic::from)?;
+
+		let mut result = errors.into_value(self.interner());
+		let mut captures = {
+			let out = match stdout_reader.join() {
+				Err(error) => std::panic::resume_unwind(error),
+				Ok(result) => result
+					.map_err(|error| Panic::io(error, pos.copy()))?
+					.into_boxed_slice(),
+			};
+
+			let err = match stderr_reader.join() {
+				Err(error) => std::panic::resume_unwind(error),
+				Ok(result) => result
+					.map_err(|error| Panic::io(error, pos.copy()))?
+					.into_boxed_slice(),
+			};
+
+			let mut dict = HashMap::new();
 
-				let mut dict = HashMap::new();
+			STDOUT.with(
+				|stdout| dict.insert(stdout.copy(), out.into())
+			);
+			STDERR.with(
+				|stderr| dict.insert(stderr.copy(), err.into())
+			);
 
-				JOIN.with(
-					|join| dict.insert(join.copy(), join_handle)
+			dict
+		};
+
+		match &mut result {
+			Value::Nil => Ok(Dict::new(captures).into()),
+			Value::Error(error) => {
+				let ctx = std::mem::take(error.context.borrow_mut().deref_mut());
+
+				ERROR.with(
+					|error| captures.insert(error.copy(), ctx)
 				);
 
-				Ok(Dict::new(dict).into())
-			}
+				*error.context.borrow_mut() = Dict::new(captures).into();
+
+				Ok(result)
+			},
+			_ => unreachable!("exec should only produce nil or error"),
 		}
 	}
 
@@ -159,7 +235,7 @@ impl Runtime {
 			)
 			.collect::<Result<_, Panic>>()?;
 
-		Ok(exec::Block { head, tail })
+		Ok(exec::Block { head, tail, job: None })
 	}
 
 
@@ -177,9 +253,15 @@ impl Runtime {
 					args.extend(arguments);
 				}
 
+				let program = match program {
+					program::command::Builtin::Alias => exec::Builtin::Alias(self.aliases.clone()),
+					program::command::Builtin::Cd => exec::Builtin::Cd,
+					program::command::Builtin::Unalias => exec::Builtin::Unalias(self.aliases.clone()),
+				};
+
 				Ok(
 					exec::Command::Builtin {
-						program: program.into(),
+						program,
 						arguments: args.into(),
 						abort_on_error: *abort_on_error,
 						pos: pos.into(),
@@ -208,7 +290,7 @@ impl Runtime {
 	) -> Result<exec::BasicCommand, Panic> {
 		let program_pos = command.program.pos.into();
 
-		let program = self.build_single_argument(
+		let mut program = self.build_single_argument(
 			&command.program,
 			|items| Panic::invalid_command_args("program", items, program_pos)
 		)?;
@@ -216,6 +298,20 @@ impl Runtime {
 		let env = self.build_env_vars(&command.env)?;
 
 		let mut args = Vec::new();
+
+		// Expand aliases. Patterns are never considered as alias names.
+		if let exec::Argument::Literal(ref name) = program {
+			if let Some(alias) = self.aliases.get(name) {
+				let mut words = alias.into_vec().into_iter().map(exec::Argument::Literal);
+
+				if let Some(word) = words.next() {
+					program = word;
+				}
+
+				args.extend(words);
+			}
+		}
+
 		for argument in command.arguments.iter() {
 			let arguments = self
 				.build_argument(argument)?
@@ -235,6 +331,7 @@ impl Runtime {
 				program,
 				env,
 				arguments: args.into(),
+				cwd: None,
 				redirections,
 				abort_on_error: command.abort_on_error,
 				pos: command.pos.into(),
@@ -285,7 +382,7 @@ impl Runtime {
 				Ok(exec::Redirection::Output { source: *source, target })
 			}
 
-			program::Redirection::Input { literal, source } => {
+			program::Redirection::Input { target, literal, source } => {
 				let pos = source.pos.into();
 
 				let source = self.build_single_argument(
@@ -293,7 +390,7 @@ impl Runtime {
 					|items| Panic::invalid_command_args("redirection", items, pos)
 				)?;
 
-				Ok(exec::Redirection::Input { literal: *literal, source })
+				Ok(exec::Redirection::Input { target: *target, literal: *literal, source })
 			}
 		}
 	}
@@ -306,6 +403,8 @@ impl Runtime {
 		match target {
 			program::RedirectionTarget::Fd(fd) => Ok(exec::RedirectionTarget::Fd(*fd)),
 
+			program::RedirectionTarget::Close => Ok(exec::RedirectionTarget::Close),
+
 			program::RedirectionTarget::Overwrite(arg) => {
 				let pos = arg.pos.into();
 
@@ -10,4 +10,6 @@ pub enum Flow {
 	Return(Value),
 	/// Break from loop.
 	Break,
+	/// Skip to the next loop iteration.
+	Continue,
 }
@@ -0,0 +1,56 @@
+use std::convert::TryFrom;
+
+
+/// Integer division, rounding towards negative infinity.
+/// Returns None on division by zero or overflow.
+pub fn checked_floor_div(int1: i64, int2: i64) -> Option<i64> {
+	let quotient = int1.checked_div(int2)?;
+
+	if int1 % int2 != 0 && (int1 < 0) != (int2 < 0) {
+		Some(quotient - 1)
+	} else {
+		Some(quotient)
+	}
+}
+
+
+/// Integer exponentiation. The exponent must not be negative.
+/// Returns None on overflow.
+pub fn checked_pow(base: i64, exp: i64) -> Option<i64> {
+	match u32::try_from(exp) {
+		Ok(exp) => base.checked_pow(exp),
+
+		// Huge exponents only don't overflow for trivial bases.
+		Err(_) => match base {
+			0 | 1 => Some(base),
+			-1 => Some(if exp % 2 == 0 { 1 } else { -1 }),
+			_ => None,
+		},
+	}
+}
+
+
+/// Left shift. The shift amount must not be negative.
+/// Returns None if any set bit would be shifted out, or if the sign would change.
+pub fn checked_shl(int: i64, amount: i64) -> Option<i64> {
+	if int == 0 {
+		return Some(0);
+	}
+
+	let amount = u32::try_from(amount).ok().filter(|&amount| amount < i64::BITS)?;
+	let result = int << amount;
+
+	if result >> amount == int {
+		Some(result)
+	} else {
+		None
+	}
+}
+
+
+/// Arithmetic right shift. The shift amount must not be negative.
+/// Shifting by more than the integer width produces either 0 or -1.
+pub fn shr(int: i64, amount: i64) -> Option<i64> {
+	let amount = amount.min(i64::BITS as i64 - 1);
+	Some(int >> amount)
+}
@@ -0,0 +1,145 @@
+use std::{
+	collections::HashMap,
+	ffi::OsStr,
+	os::unix::ffi::OsStrExt,
+};
+
+use gc::{Finalize, Trace};
+
+use super::{
+	CallContext,
+	Dict,
+	RustFun,
+	NativeFun,
+	Panic,
+	Value,
+};
+
+
+inventory::submit! { RustFun::from(Set) }
+inventory::submit! { RustFun::from(Get) }
+inventory::submit! { RustFun::from(Remove) }
+inventory::submit! { RustFun::from(List) }
+
+#[derive(Trace, Finalize)]
+struct Set;
+
+impl NativeFun for Set {
+	fn name(&self) -> &'static str { "std.alias.set" }
+
+	fn call(&self, context: CallContext) -> Result<Value, Panic> {
+		let (name, alias) = match context.args() {
+			[ Value::String(ref name), Value::String(ref word) ] => (
+				name.copy(),
+				vec![ Box::from(OsStr::from_bytes(word.as_bytes())) ],
+			),
+
+			[ Value::String(ref name), Value::Array(ref array) ] => {
+				let mut words = Vec::new();
+
+				for word in array.borrow().iter() {
+					match word {
+						Value::String(ref word) => words.push(OsStr::from_bytes(word.as_bytes()).into()),
+						other => return Err(Panic::type_error(other.copy(), "string", context.pos.copy())),
+					}
+				}
+
+				(name.copy(), words)
+			}
+
+			[ Value::String(_), other ] => return Err(
+				Panic::type_error(other.copy(), "string or array", context.pos)
+			),
+			[ other, _ ] => return Err(Panic::type_error(other.copy(), "string", context.pos)),
+			args => return Err(Panic::invalid_args(args.len() as u32, 2, context.pos)),
+		};
+
+		if name.is_empty() {
+			return Err(Panic::value_error(name.into(), "non-empty alias name", context.pos));
+		}
+
+		if alias.is_empty() {
+			return Err(Panic::value_error(context.args()[1].copy(), "non-empty alias", context.pos));
+		}
+
+		context.runtime.aliases.insert(
+			OsStr::from_bytes(name.as_bytes()).into(),
+			alias.into(),
+		);
+
+		Ok(Value::default())
+	}
+}
+
+#[derive(Trace, Finalize)]
+struct Get;
+
+impl NativeFun for Get {
+	fn name(&self) -> &'static str { "std.alias.get" }
+
+	fn call(&self, context: CallContext) -> Result<Value, Panic> {
+		match context.args() {
+			[ Value::String(ref name) ] => Ok(
+				context.runtime.aliases
+					.get(OsStr::from_bytes(name.as_bytes()))
+					.map(|alias| to_value(&alias))
+					.unwrap_or_default()
+			),
+
+			[ other ] => Err(Panic::type_error(other.copy(), "string", context.pos)),
+			args => Err(Panic::invalid_args(args.len() as u32, 1, context.pos))
+		}
+	}
+}
+
+#[derive(Trace, Finalize)]
+struct Remove;
+
+impl NativeFun for Remove {
+	fn name(&self) -> &'static str { "std.alias.remove" }
+
+	fn call(&self, context: CallContext) -> Result<Value, Panic> {
+		match context.args() {
+			[ Value::String(ref name) ] => Ok(
+				context.runtime.aliases
+					.remove(OsStr::from_bytes(name.as_bytes()))
+					.into()
+			),
+
+			[ other ] => Err(Panic::type_error(other.copy(), "string", context.pos)),
+			args => Err(Panic::invalid_args(args.len() as u32, 1, context.pos))
+		}
+	}
+}
+
+#[derive(Trace, Finalize)]
+struct List;
+
+impl NativeFun for List {
+	fn name(&self) -> &'static str { "std.alias.list" }
+
+	fn call(&self, context: CallContext) -> Result<Value, Panic> {
+		let args = context.args();
+		if !args.is_empty() {
+			return Err(Panic::invalid_args(args.len() as u32, 0, context.pos));
+		}
+
+		let aliases: HashMap<Value, Value> = context.runtime.aliases
+			.list()
+			.into_iter()
+			.map(|(name, alias)| (name.as_bytes().into(), to_value(&alias)))
+			.collect();
+
+		Ok(Dict::new(aliases).into())
+	}
+}
+
+
+/// Convert the words of an alias to an array of strings.
+fn to_value(alias: &[Box<OsStr>]) -> Value {
+	alias
+		.iter()
+		.map(|word| Value::from(word.as_bytes()))
+		.collect::<Vec<Value>>()
+		.into()
+}
@@ -4,6 +4,7 @@ use crate::fmt;
 
 use super::{
 	CallContext,
+	Dict,
 	Error,
 	NativeFun,
 	RustFun,
@@ -14,6 +15,8 @@ use super::{
 
 inventory::submit!{ RustFun::from(Catch) }
 
+/// Call a function, converting a panic into an error. The error context is the "panic"
+/// string, or a dict with the backtrace if the optional second argument is true.
 #[derive(Trace, Finalize)]
 struct Catch;
 
@@ -23,19 +26,25 @@ impl NativeFun for Catch {
 	fn call(&self, mut context: CallContext) -> Result<Value, Panic> {
 		thread_local! {
 			pub static PANIC: Value = "panic".into();
+			pub static BACKTRACE: Value = "backtrace".into();
+			pub static CALLEE: Value = "callee".into();
+			pub static POS: Value = "pos".into();
 		}
 
-		let fun = match context.args() {
-			[ Value::Function(fun) ] => fun.copy(),
+		let (fun, with_backtrace) = match context.args() {
+			[ Value::Function(fun) ] => (fun.copy(), false),
+			[ Value::Function(fun), Value::Bool(with_backtrace) ] => (fun.copy(), *with_backtrace),
 
-			[ other ] => return Err(Panic::type_error(other.copy(), "function", context.pos)),
-			args => return Err(Panic::invalid_args(args.len() as u32, 1, context.pos))
+			[ Value::Function(_), other ] => return Err(Panic::type_error(other.copy(), "bool", context.pos)),
+			[ other ] | [ other, _ ] => return Err(Panic::type_error(other.copy(), "function", context.pos)),
+			args => return Err(Panic::invalid_args(args.len() as u32, 2, context.pos))
 		};
 
+		let args_count = context.args().len();
 		let result = context.call(
 			Value::default(),
 			&fun,
-			context.args_start + 1
+			context.args_start + args_count
 		);
 
 		match result {
@@ -44,14 +53,38 @@ impl NativeFun for Catch {
 			Err(panic) => {
 				let description = format!(
 					"caught panic: {}",
-					fmt::Show(panic, context.interner()),
+					fmt::Show(panic.kind.as_ref(), context.interner()),
+				);
+
+				if !with_backtrace {
+					return Ok(Error::new(description.into(), PANIC.with(Value::copy)).into());
+				}
+
+				let backtrace: Vec<Value> = panic.backtrace
+					.into_iter()
+					.map(
+						|frame| {
+							let function = frame.function.into();
+							let pos = fmt::Show(frame.call_site, context.interner()).to_string().into();
+
+							let dict = Dict::default();
+							CALLEE.with(|key| dict.insert(key.copy(), function));
+							POS.with(|key| dict.insert(key.copy(), pos));
+							dict.into()
+						}
+					)
+					.collect();
+
+				let error_context = Dict::default();
+				BACKTRACE.with(
+					|key| error_context.insert(key.copy(), backtrace.into())
 				);
 
 				Ok(
 					Value::from(
 						Error::new(
 							description.into(),
-							PANIC.with(Value::copy),
+							error_context.into(),
 						)
 					)
 				)
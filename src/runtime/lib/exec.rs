@@ -0,0 +1,136 @@
+use std::{
+	ffi::OsStr,
+	os::unix::ffi::OsStrExt,
+};
+
+use gc::{Finalize, Trace};
+
+use crate::runtime::command::ExecOptions;
+use super::{
+	CallContext,
+	Dict,
+	NativeFun,
+	RustFun,
+	Panic,
+	Value,
+};
+
+
+inventory::submit! { RustFun::from(Exec) }
+
+
+thread_local! {
+	static PROGRAM: Value = "program".into();
+	static ARGS: Value = "args".into();
+	static STDIN: Value = "stdin".into();
+	static ENV: Value = "env".into();
+	static CWD: Value = "cwd".into();
+}
+
+
+/// Execute a program, capturing its output. Accepts a dict with the following keys:
+/// - program: the program to execute.
+/// - args: optional array of arguments.
+/// - stdin: optional string to be written to the program's stdin. If omitted, stdin is
+///   inherited.
+/// - env: optional dict of environment variables.
+/// - cwd: optional working directory.
+///
+/// The result has the same shape as the one of capture blocks.
+#[derive(Trace, Finalize)]
+struct Exec;
+
+impl Exec {
+	fn options(dict: &Dict, context: &CallContext) -> Result<ExecOptions, Panic> {
+		let pos = || context.pos.copy();
+		let get = |key: &'static std::thread::LocalKey<Value>| key.with(
+			|key| dict.get(key).unwrap_or_default()
+		);
+
+		for key in dict.borrow().keys() {
+			let known = [&PROGRAM, &ARGS, &STDIN, &ENV, &CWD]
+				.iter()
+				.any(|option| option.with(|option| option == key));
+
+			if !known {
+				return Err(Panic::value_error(key.copy(), "exec option", pos()));
+			}
+		}
+
+		let program = match get(&PROGRAM) {
+			Value::String(ref string) => as_os_str(string.as_bytes()),
+			Value::Nil => return Err(
+				Panic::value_error(dict.copy().into(), "dict with program", pos())
+			),
+			other => return Err(Panic::type_error(other, "string", pos())),
+		};
+
+		let args = match get(&ARGS) {
+			Value::Nil => Default::default(),
+			Value::Array(ref array) => array
+				.borrow()
+				.iter()
+				.map(
+					|arg| match arg {
+						Value::String(ref string) => Ok(as_os_str(string.as_bytes())),
+						other => Err(Panic::type_error(other.copy(), "string", pos())),
+					}
+				)
+				.collect::<Result<_, Panic>>()?,
+			other => return Err(Panic::type_error(other, "array", pos())),
+		};
+
+		let stdin = match get(&STDIN) {
+			Value::Nil => None,
+			Value::String(ref string) => Some(string.as_bytes().into()),
+			other => return Err(Panic::type_error(other, "string", pos())),
+		};
+
+		let env = match get(&ENV) {
+			Value::Nil => Default::default(),
+			Value::Dict(ref env) => env
+				.borrow()
+				.iter()
+				.map(
+					|entry| match entry {
+						(Value::String(ref key), Value::String(ref value)) => Ok(
+							(as_os_str(key.as_bytes()), as_os_str(value.as_bytes()))
+						),
+						(Value::String(_), other) | (other, _) => Err(
+							Panic::type_error(other.copy(), "string", pos())
+						),
+					}
+				)
+				.collect::<Result<_, Panic>>()?,
+			other => return Err(Panic::type_error(other, "dict", pos())),
+		};
+
+		let cwd = match get(&CWD) {
+			Value::Nil => None,
+			Value::String(ref string) => Some(as_os_str(string.as_bytes())),
+			other => return Err(Panic::type_error(other, "string", pos())),
+		};
+
+		Ok(ExecOptions { program, args, env, cwd, stdin })
+	}
+}
+
+impl NativeFun for Exec {
+	fn name(&self) -> &'static str { "std.exec" }
+
+	fn call(&self, context: CallContext) -> Result<Value, Panic> {
+		let options = match context.args() {
+			[ Value::Dict(ref dict) ] => Self::options(dict, &context)?,
+
+			[ other ] => return Err(Panic::type_error(other.copy(), "dict", context.pos)),
+			args => return Err(Panic::invalid_args(args.len() as u32, 1, context.pos))
+		};
+
+		context.runtime.exec_program(options, context.pos)
+	}
+}
+
+
+fn as_os_str(bytes: &[u8]) -> Box<OsStr> {
+	OsStr::from_bytes(bytes).into()
+}
@@ -0,0 +1,47 @@
+use gc::{Finalize, Trace};
+
+use super::{
+	CallContext,
+	NativeFun,
+	RustFun,
+	Panic,
+	Value,
+};
+
+
+inventory::submit! { RustFun::from(Finally) }
+
+/// Call a function, and then call the cleanup function regardless of how the former
+/// finished: by returning, through the try operator or by panicking. Panics are
+/// propagated after the cleanup, unless the cleanup panics itself. Exiting the process
+/// skips the cleanup.
+#[derive(Trace, Finalize)]
+struct Finally;
+
+impl NativeFun for Finally {
+	fn name(&self) -> &'static str { "std.finally" }
+
+	fn call(&self, mut context: CallContext) -> Result<Value, Panic> {
+		let (body, cleanup) = match context.args() {
+			[ Value::Function(body), Value::Function(cleanup) ] => (body.copy(), cleanup.copy()),
+
+			[ Value::Function(_), other ] | [ other, _ ] =>
+				return Err(Panic::type_error(other.copy(), "function", context.pos)),
+			args => return Err(Panic::invalid_args(args.len() as u32, 2, context.pos))
+		};
+
+		let result = context.call(
+			Value::default(),
+			&body,
+			context.args_start + 2
+		);
+
+		context.call(
+			Value::default(),
+			&cleanup,
+			context.args_start + 2
+		)?;
+
+		result
+	}
+}
@@ -0,0 +1,449 @@
+use std::{
+	collections::HashMap,
+	ffi::OsStr,
+	fs::{self, OpenOptions, Permissions},
+	io,
+	os::unix::fs::{MetadataExt, PermissionsExt},
+	path::{Path, PathBuf},
+};
+
+use gc::{Finalize, Trace};
+
+use super::{
+	CallContext,
+	Dict,
+	Error,
+	Float,
+	NativeFun,
+	Panic,
+	RustFun,
+	Str,
+	Value,
+};
+
+
+inventory::submit! { RustFun::from(Read) }
+inventory::submit! { RustFun::from(Write) }
+inventory::submit! { RustFun::from(Append) }
+inventory::submit! { RustFun::from(Exists) }
+inventory::submit! { RustFun::from(Stat) }
+inventory::submit! { RustFun::from(Mkdir) }
+inventory::submit! { RustFun::from(Remove) }
+inventory::submit! { RustFun::from(Rename) }
+inventory::submit! { RustFun::from(CopyFile) }
+inventory::submit! { RustFun::from(ListDir) }
+inventory::submit! { RustFun::from(Walk) }
+inventory::submit! { RustFun::from(Symlink) }
+inventory::submit! { RustFun::from(ReadLink) }
+inventory::submit! { RustFun::from(Chmod) }
+
+
+#[derive(Trace, Finalize)]
+struct Read;
+
+impl NativeFun for Read {
+	fn name(&self) -> &'static str { "std.fs.read" }
+
+	fn call(&self, context: CallContext) -> Result<Value, Panic> {
+		match context.args() {
+			[ path @ Value::String(ref string) ] => Ok(
+				to_value(
+					fs::read(as_path(string)).map(Vec::into_boxed_slice),
+					path
+				)
+			),
+
+			[ other ] => Err(Panic::type_error(other.copy(), "string", context.pos)),
+			args => Err(Panic::invalid_args(args.len() as u32, 1, context.pos))
+		}
+	}
+}
+
+
+#[derive(Trace, Finalize)]
+struct Write;
+
+impl NativeFun for Write {
+	fn name(&self) -> &'static str { "std.fs.write" }
+
+	fn call(&self, context: CallContext) -> Result<Value, Panic> {
+		match context.args() {
+			[ path @ Value::String(ref string), Value::String(ref contents) ] => Ok(
+				to_value(fs::write(as_path(string), contents), path)
+			),
+
+			[ Value::String(_), other ] => Err(Panic::type_error(other.copy(), "string", context.pos)),
+			[ other, _ ] => Err(Panic::type_error(other.copy(), "string", context.pos)),
+			args => Err(Panic::invalid_args(args.len() as u32, 2, context.pos))
+		}
+	}
+}
+
+
+#[derive(Trace, Finalize)]
+struct Append;
+
+impl NativeFun for Append {
+	fn name(&self) -> &'static str { "std.fs.append" }
+
+	fn call(&self, context: CallContext) -> Result<Value, Panic> {
+		match context.args() {
+			[ path @ Value::String(ref string), Value::String(ref contents) ] => {
+				let result = OpenOptions::new()
+					.create(true)
+					.append(true)
+					.open(as_path(string))
+					.and_then(|mut file| io::Write::write_all(&mut file, contents.as_bytes()));
+
+				Ok(to_value(result, path))
+			}
+
+			[ Value::String(_), other ] => Err(Panic::type_error(other.copy(), "string", context.pos)),
+			[ other, _ ] => Err(Panic::type_error(other.copy(), "string", context.pos)),
+			args => Err(Panic::invalid_args(args.len() as u32, 2, context.pos))
+		}
+	}
+}
+
+
+#[derive(Trace, Finalize)]
+struct Exists;
+
+impl NativeFun for Exists {
+	fn name(&self) -> &'static str { "std.fs.exists" }
+
+	fn call(&self, context: CallContext) -> Result<Value, Panic> {
+		match context.args() {
+			[ Value::String(ref string) ] => Ok(as_path(string).exists().into()),
+
+			[ other ] => Err(Panic::type_error(other.copy(), "string", context.pos)),
+			args => Err(Panic::invalid_args(args.len() as u32, 1, context.pos))
+		}
+	}
+}
+
+
+#[derive(Trace, Finalize)]
+struct Stat;
+
+impl Stat {
+	fn stat(path: &Path) -> io::Result<Value> {
+		thread_local! {
+			pub static TYPE: Value = "type".into();
+			pub static SIZE: Value = "size".into();
+			pub static MODE: Value = "mode".into();
+			pub static UID: Value = "uid".into();
+			pub static GID: Value = "gid".into();
+			pub static MODIFIED: Value = "modified".into();
+			pub static ACCESSED: Value = "accessed".into();
+		}
+
+		// Don't follow symlinks, so that they can be told apart.
+		let metadata = fs::symlink_metadata(path)?;
+
+		let file_type = metadata.file_type();
+		let file_type =
+			if file_type.is_file() {
+				"file"
+			} else if file_type.is_dir() {
+				"dir"
+			} else if file_type.is_symlink() {
+				"symlink"
+			} else {
+				"other"
+			};
+
+		let timestamp = |seconds: i64, nanoseconds: i64| {
+			Value::Float(Float(seconds as f64 + nanoseconds as f64 * 1e-9))
+		};
+
+		let mut dict = HashMap::new();
+
+		TYPE.with(|key| dict.insert(key.copy(), file_type.into()));
+		SIZE.with(|key| dict.insert(key.copy(), Value::Int(metadata.size() as i64)));
+		MODE.with(|key| dict.insert(key.copy(), Value::Int((metadata.mode() & 0o7777) as i64)));
+		UID.with(|key| dict.insert(key.copy(), Value::Int(metadata.uid() as i64)));
+		GID.with(|key| dict.insert(key.copy(), Value::Int(metadata.gid() as i64)));
+		MODIFIED.with(
+			|key| dict.insert(key.copy(), timestamp(metadata.mtime(), metadata.mtime_nsec()))
+		);
+		ACCESSED.with(
+			|key| dict.insert(key.copy(), timestamp(metadata.atime(), metadata.atime_nsec()))
+		);
+
+		Ok(Dict::new(dict).into())
+	}
+}
+
+impl NativeFun for Stat {
+	fn name(&self) -> &'static str { "std.fs.stat" }
+
+	fn call(&self, context: CallContext) -> Result<Value, Panic> {
+		match context.args() {
+			[ path @ Value::String(ref string) ] => Ok(to_value(Self::stat(as_path(string)), path)),
+
+			[ other ] => Err(Panic::type_error(other.copy(), "string", context.pos)),
+			args => Err(Panic::invalid_args(args.len() as u32, 1, context.pos))
+		}
+	}
+}
+
+
+#[derive(Trace, Finalize)]
+struct Mkdir;
+
+impl NativeFun for Mkdir {
+	fn name(&self) -> &'static str { "std.fs.mkdir" }
+
+	fn call(&self, context: CallContext) -> Result<Value, Panic> {
+		match context.args() {
+			[ path @ Value::String(ref string) ] => Ok(
+				to_value(fs::create_dir_all(as_path(string)), path)
+			),
+
+			[ other ] => Err(Panic::type_error(other.copy(), "string", context.pos)),
+			args => Err(Panic::invalid_args(args.len() as u32, 1, context.pos))
+		}
+	}
+}
+
+
+#[derive(Trace, Finalize)]
+struct Remove;
+
+impl Remove {
+	fn remove(path: &Path) -> io::Result<()> {
+		// Don't follow symlinks, otherwise we would remove the contents of the linked dir.
+		if fs::symlink_metadata(path)?.is_dir() {
+			fs::remove_dir_all(path)
+		} else {
+			fs::remove_file(path)
+		}
+	}
+}
+
+impl NativeFun for Remove {
+	fn name(&self) -> &'static str { "std.fs.remove" }
+
+	fn call(&self, context: CallContext) -> Result<Value, Panic> {
+		match context.args() {
+			[ path @ Value::String(ref string) ] => Ok(to_value(Self::remove(as_path(string)), path)),
+
+			[ other ] => Err(Panic::type_error(other.copy(), "string", context.pos)),
+			args => Err(Panic::invalid_args(args.len() as u32, 1, context.pos))
+		}
+	}
+}
+
+
+#[derive(Trace, Finalize)]
+struct Rename;
+
+impl NativeFun for Rename {
+	fn name(&self) -> &'static str { "std.fs.rename" }
+
+	fn call(&self, context: CallContext) -> Result<Value, Panic> {
+		match context.args() {
+			[ path @ Value::String(ref from), Value::String(ref to) ] => Ok(
+				to_value(fs::rename(as_path(from), as_path(to)), path)
+			),
+
+			[ Value::String(_), other ] => Err(Panic::type_error(other.copy(), "string", context.pos)),
+			[ other, _ ] => Err(Panic::type_error(other.copy(), "string", context.pos)),
+			args => Err(Panic::invalid_args(args.len() as u32, 2, context.pos))
+		}
+	}
+}
+
+
+#[derive(Trace, Finalize)]
+struct CopyFile;
+
+impl NativeFun for CopyFile {
+	fn name(&self) -> &'static str { "std.fs.copy" }
+
+	fn call(&self, context: CallContext) -> Result<Value, Panic> {
+		match context.args() {
+			[ path @ Value::String(ref from), Value::String(ref to) ] => Ok(
+				to_value(fs::copy(as_path(from), as_path(to)).map(|_| ()), path)
+			),
+
+			[ Value::String(_), other ] => Err(Panic::type_error(other.copy(), "string", context.pos)),
+			[ other, _ ] => Err(Panic::type_error(other.copy(), "string", context.pos)),
+			args => Err(Panic::invalid_args(args.len() as u32, 2, context.pos))
+		}
+	}
+}
+
+
+#[derive(Trace, Finalize)]
+struct ListDir;
+
+impl ListDir {
+	fn list_dir(path: &Path) -> io::Result<Value> {
+		let mut entries = fs::read_dir(path)?
+			.map(|entry| entry.map(|entry| entry.file_name()))
+			.collect::<io::Result<Vec<_>>>()?;
+
+		entries.sort();
+
+		Ok(
+			entries
+				.into_iter()
+				.map(Value::from)
+				.collect::<Vec<Value>>()
+				.into()
+		)
+	}
+}
+
+impl NativeFun for ListDir {
+	fn name(&self) -> &'static str { "std.fs.list_dir" }
+
+	fn call(&self, context: CallContext) -> Result<Value, Panic> {
+		match context.args() {
+			[ path @ Value::String(ref string) ] => Ok(to_value(Self::list_dir(as_path(string)), path)),
+
+			[ other ] => Err(Panic::type_error(other.copy(), "string", context.pos)),
+			args => Err(Panic::invalid_args(args.len() as u32, 1, context.pos))
+		}
+	}
+}
+
+
+#[derive(Trace, Finalize)]
+struct Walk;
+
+impl Walk {
+	/// Collect the paths of all entries under the given dir, recursively.
+	/// Symlinks to directories are not followed.
+	fn walk(path: &Path, paths: &mut Vec<PathBuf>) -> io::Result<()> {
+		let mut entries = fs::read_dir(path)?
+			.collect::<io::Result<Vec<_>>>()?;
+
+		entries.sort_by_key(fs::DirEntry::file_name);
+
+		for entry in entries {
+			let path = entry.path();
+			let is_dir = entry.file_type()?.is_dir();
+
+			paths.push(path);
+
+			if is_dir {
+				let path = paths.last().expect("path was just pushed").clone();
+				Self::walk(&path, paths)?;
+			}
+		}
+
+		Ok(())
+	}
+}
+
+impl NativeFun for Walk {
+	fn name(&self) -> &'static str { "std.fs.walk" }
+
+	fn call(&self, context: CallContext) -> Result<Value, Panic> {
+		match context.args() {
+			[ path @ Value::String(ref string) ] => {
+				let mut paths = Vec::new();
+
+				let result = Self::walk(as_path(string), &mut paths)
+					.map(
+						|()| paths
+							.into_iter()
+							.map(|path| Value::String(path.into()))
+							.collect::<Vec<Value>>()
+					);
+
+				Ok(to_value(result, path))
+			}
+
+			[ other ] => Err(Panic::type_error(other.copy(), "string", context.pos)),
+			args => Err(Panic::invalid_args(args.len() as u32, 1, context.pos))
+		}
+	}
+}
+
+
+#[derive(Trace, Finalize)]
+struct Symlink;
+
+impl NativeFun for Symlink {
+	fn name(&self) -> &'static str { "std.fs.symlink" }
+
+	fn call(&self, context: CallContext) -> Result<Value, Panic> {
+		match context.args() {
+			[ Value::String(ref target), path @ Value::String(ref link) ] => Ok(
+				to_value(std::os::unix::fs::symlink(as_path(target), as_path(link)), path)
+			),
+
+			[ Value::String(_), other ] => Err(Panic::type_error(other.copy(), "string", context.pos)),
+			[ other, _ ] => Err(Panic::type_error(other.copy(), "string", context.pos)),
+			args => Err(Panic::invalid_args(args.len() as u32, 2, context.pos))
+		}
+	}
+}
+
+
+#[derive(Trace, Finalize)]
+struct ReadLink;
+
+impl NativeFun for ReadLink {
+	fn name(&self) -> &'static str { "std.fs.read_link" }
+
+	fn call(&self, context: CallContext) -> Result<Value, Panic> {
+		match context.args() {
+			[ path @ Value::String(ref string) ] => Ok(
+				to_value(
+					fs::read_link(as_path(string)).map(|target| Value::String(target.into())),
+					path
+				)
+			),
+
+			[ other ] => Err(Panic::type_error(other.copy(), "string", context.pos)),
+			args => Err(Panic::invalid_args(args.len() as u32, 1, context.pos))
+		}
+	}
+}
+
+
+#[derive(Trace, Finalize)]
+struct Chmod;
+
+impl NativeFun for Chmod {
+	fn name(&self) -> &'static str { "std.fs.chmod" }
+
+	fn call(&self, context: CallContext) -> Result<Value, Panic> {
+		match context.args() {
+			[ Value::String(_), mode @ Value::Int(i) ] if !(0 ..= 0o7777).contains(i) => Err(
+				Panic::value_error(mode.copy(), "valid file mode", context.pos)
+			),
+
+			[ path @ Value::String(ref string), Value::Int(mode) ] => Ok(
+				to_value(
+					fs::set_permissions(as_path(string), Permissions::from_mode(*mode as u32)),
+					path
+				)
+			),
+
+			[ Value::String(_), other ] => Err(Panic::type_error(other.copy(), "int", context.pos)),
+			[ other, _ ] => Err(Panic::type_error(other.copy(), "string", context.pos)),
+			args => Err(Panic::invalid_args(args.len() as u32, 2, context.pos))
+		}
+	}
+}
+
+
+fn as_path(string: &Str) -> &Path {
+	Path::new(AsRef::<OsStr>::as_ref(string))
+}
+
+
+/// Convert the result of a filesystem operation to a value.
+/// Errors carry the path as context.
+fn to_value<T: Into<Value>>(result: io::Result<T>, path: &Value) -> Value {
+	match result {
+		Ok(value) => value.into(),
+		Err(error) => Error::new(error.to_string().into(), path.copy()).into(),
+	}
+}
@@ -8,7 +8,7 @@ use std::{
 use gc::{Finalize, Trace};
 
 use crate::{
-	fmt,
+	diagnostics,
 	syntax,
 	semantic,
 	symbol::{self, Symbol}
@@ -87,6 +87,8 @@ impl Import {
 				|error| Panic::io(error, context.pos.copy())
 			)?;
 
+		context.runtime.sources_mut().insert(&source);
+
 		// Syntax.
 		let syntactic_analysis = syntax::Analysis::analyze(
 			&source,
@@ -95,13 +97,13 @@ impl Import {
 		let has_syntax_errors = !syntactic_analysis.is_ok();
 
 		if has_syntax_errors {
-			eprint!("{}", fmt::Show(
-				syntactic_analysis.errors,
-				syntax::AnalysisDisplayContext {
-					max_errors: Some(20),
-					interner: context.runtime.interner(),
-				}
-			));
+			diagnostics::syntax_errors(
+				&syntactic_analysis.errors,
+				&source,
+				context.runtime.diagnostics,
+				context.runtime.interner(),
+				context.runtime.sources(),
+			);
 			return Err(Panic::import_failed(path, context.pos.copy()));
 		}
 
@@ -112,18 +114,24 @@ impl Import {
 			)
 			.map_err(
 				|errors| {
-					eprint!("{}", fmt::Show(
-						errors,
-						semantic::ErrorsDisplayContext {
-							max_errors: Some(20),
-							interner: context.runtime.interner(),
-						}
-					));
+					diagnostics::semantic_errors(
+						&errors,
+						context.runtime.diagnostics,
+						context.runtime.interner(),
+						context.runtime.sources(),
+					);
 
 					Panic::import_failed(path, context.pos.copy())
 				}
 			)?;
 
+		diagnostics::semantic_warnings(
+			&program.warnings,
+			context.runtime.diagnostics,
+			context.runtime.interner(),
+			context.runtime.sources(),
+		);
+
 		// Eval.
 		let program = Box::leak(Box::new(program));
 		context.runtime.eval(program)
@@ -0,0 +1,27 @@
+use gc::{Finalize, Trace};
+
+use super::{
+	CallContext,
+	NativeFun,
+	RustFun,
+	Panic,
+	Value,
+};
+
+
+inventory::submit! { RustFun::from(Jobs) }
+
+/// List the asynchronous command blocks which are still running.
+#[derive(Trace, Finalize)]
+struct Jobs;
+
+impl NativeFun for Jobs {
+	fn name(&self) -> &'static str { "std.jobs" }
+
+	fn call(&self, context: CallContext) -> Result<Value, Panic> {
+		match context.args() {
+			[] => Ok(context.runtime.jobs.list()),
+			args => Err(Panic::invalid_args(args.len() as u32, 0, context.pos))
+		}
+	}
+}
@@ -0,0 +1,292 @@
+use std::{
+	ffi::OsStr,
+	os::unix::ffi::OsStrExt,
+	path::{Component, Path, PathBuf},
+};
+
+use gc::{Finalize, Trace};
+
+use super::{
+	CallContext,
+	Error,
+	NativeFun,
+	Panic,
+	RustFun,
+	Str,
+	Value,
+};
+
+
+inventory::submit! { RustFun::from(Join) }
+inventory::submit! { RustFun::from(Parent) }
+inventory::submit! { RustFun::from(FileName) }
+inventory::submit! { RustFun::from(Stem) }
+inventory::submit! { RustFun::from(Extension) }
+inventory::submit! { RustFun::from(WithExtension) }
+inventory::submit! { RustFun::from(IsAbsolute) }
+inventory::submit! { RustFun::from(Canonicalize) }
+inventory::submit! { RustFun::from(RelativeTo) }
+inventory::submit! { RustFun::from(Split) }
+
+
+#[derive(Trace, Finalize)]
+struct Join;
+
+impl NativeFun for Join {
+	fn name(&self) -> &'static str { "std.path.join" }
+
+	fn call(&self, context: CallContext) -> Result<Value, Panic> {
+		let args = context.args();
+		if args.is_empty() {
+			return Err(Panic::invalid_args(0, 1, context.pos));
+		}
+
+		let mut path = PathBuf::new();
+
+		for arg in args {
+			match arg {
+				Value::String(ref string) => path.push(as_path(string)),
+				other => return Err(Panic::type_error(other.copy(), "string", context.pos)),
+			}
+		}
+
+		Ok(Value::String(path.into()))
+	}
+}
+
+
+#[derive(Trace, Finalize)]
+struct Parent;
+
+impl NativeFun for Parent {
+	fn name(&self) -> &'static str { "std.path.parent" }
+
+	fn call(&self, context: CallContext) -> Result<Value, Panic> {
+		match context.args() {
+			[ Value::String(ref string) ] => Ok(
+				as_path(string)
+					.parent()
+					.map(|parent| parent.as_os_str().as_bytes().into())
+					.unwrap_or_default()
+			),
+
+			[ other ] => Err(Panic::type_error(other.copy(), "string", context.pos)),
+			args => Err(Panic::invalid_args(args.len() as u32, 1, context.pos))
+		}
+	}
+}
+
+
+#[derive(Trace, Finalize)]
+struct FileName;
+
+impl NativeFun for FileName {
+	fn name(&self) -> &'static str { "std.path.file_name" }
+
+	fn call(&self, context: CallContext) -> Result<Value, Panic> {
+		match context.args() {
+			[ Value::String(ref string) ] => Ok(to_value(as_path(string).file_name())),
+
+			[ other ] => Err(Panic::type_error(other.copy(), "string", context.pos)),
+			args => Err(Panic::invalid_args(args.len() as u32, 1, context.pos))
+		}
+	}
+}
+
+
+#[derive(Trace, Finalize)]
+struct Stem;
+
+impl NativeFun for Stem {
+	fn name(&self) -> &'static str { "std.path.stem" }
+
+	fn call(&self, context: CallContext) -> Result<Value, Panic> {
+		match context.args() {
+			[ Value::String(ref string) ] => Ok(to_value(as_path(string).file_stem())),
+
+			[ other ] => Err(Panic::type_error(other.copy(), "string", context.pos)),
+			args => Err(Panic::invalid_args(args.len() as u32, 1, context.pos))
+		}
+	}
+}
+
+
+#[derive(Trace, Finalize)]
+struct Extension;
+
+impl NativeFun for Extension {
+	fn name(&self) -> &'static str { "std.path.extension" }
+
+	fn call(&self, context: CallContext) -> Result<Value, Panic> {
+		match context.args() {
+			[ Value::String(ref string) ] => Ok(to_value(as_path(string).extension())),
+
+			[ other ] => Err(Panic::type_error(other.copy(), "string", context.pos)),
+			args => Err(Panic::invalid_args(args.len() as u32, 1, context.pos))
+		}
+	}
+}
+
+
+#[derive(Trace, Finalize)]
+struct WithExtension;
+
+impl NativeFun for WithExtension {
+	fn name(&self) -> &'static str { "std.path.with_extension" }
+
+	fn call(&self, context: CallContext) -> Result<Value, Panic> {
+		match context.args() {
+			[ Value::String(ref string), Value::String(ref extension) ] => Ok(
+				Value::String(
+					as_path(string)
+						.with_extension(AsRef::<OsStr>::as_ref(extension))
+						.into()
+				)
+			),
+
+			[ Value::String(_), other ] => Err(Panic::type_error(other.copy(), "string", context.pos)),
+			[ other, _ ] => Err(Panic::type_error(other.copy(), "string", context.pos)),
+			args => Err(Panic::invalid_args(args.len() as u32, 2, context.pos))
+		}
+	}
+}
+
+
+#[derive(Trace, Finalize)]
+struct IsAbsolute;
+
+impl NativeFun for IsAbsolute {
+	fn name(&self) -> &'static str { "std.path.is_absolute" }
+
+	fn call(&self, context: CallContext) -> Result<Value, Panic> {
+		match context.args() {
+			[ Value::String(ref string) ] => Ok(as_path(string).is_absolute().into()),
+
+			[ other ] => Err(Panic::type_error(other.copy(), "string", context.pos)),
+			args => Err(Panic::invalid_args(args.len() as u32, 1, context.pos))
+		}
+	}
+}
+
+
+#[derive(Trace, Finalize)]
+struct Canonicalize;
+
+impl NativeFun for Canonicalize {
+	fn name(&self) -> &'static str { "std.path.canonicalize" }
+
+	fn call(&self, context: CallContext) -> Result<Value, Panic> {
+		match context.args() {
+			[ path @ Value::String(ref string) ] => Ok(
+				match as_path(string).canonicalize() {
+					Ok(path) => Value::String(path.into()),
+					Err(error) => Error::new(error.to_string().into(), path.copy()).into(),
+				}
+			),
+
+			[ other ] => Err(Panic::type_error(other.copy(), "string", context.pos)),
+			args => Err(Panic::invalid_args(args.len() as u32, 1, context.pos))
+		}
+	}
+}
+
+
+#[derive(Trace, Finalize)]
+struct RelativeTo;
+
+impl RelativeTo {
+	/// Lexically compute the path relative to the base. Both paths must be either
+	/// absolute or relative, and the base must not contain `..` components after the
+	/// common prefix.
+	fn relative_to(path: &Path, base: &Path) -> Option<PathBuf> {
+		if path.is_absolute() != base.is_absolute() {
+			return None;
+		}
+
+		let mut path = path.components().peekable();
+		let mut base = base.components().peekable();
+
+		// Skip the common prefix.
+		while let (Some(a), Some(b)) = (path.peek(), base.peek()) {
+			if a != b {
+				break;
+			}
+
+			path.next();
+			base.next();
+		}
+
+		let mut relative = PathBuf::new();
+
+		for component in base {
+			match component {
+				Component::Normal(_) => relative.push(".."),
+				Component::CurDir => (),
+				_ => return None,
+			}
+		}
+
+		relative.extend(path);
+
+		if relative.as_os_str().is_empty() {
+			relative.push(".");
+		}
+
+		Some(relative)
+	}
+}
+
+impl NativeFun for RelativeTo {
+	fn name(&self) -> &'static str { "std.path.relative_to" }
+
+	fn call(&self, context: CallContext) -> Result<Value, Panic> {
+		match context.args() {
+			[ path @ Value::String(ref string), Value::String(ref base) ] => Ok(
+				match Self::relative_to(as_path(string), as_path(base)) {
+					Some(path) => Value::String(path.into()),
+					None => Error::new("path is not relative to base".into(), path.copy()).into(),
+				}
+			),
+
+			[ Value::String(_), other ] => Err(Panic::type_error(other.copy(), "string", context.pos)),
+			[ other, _ ] => Err(Panic::type_error(other.copy(), "string", context.pos)),
+			args => Err(Panic::invalid_args(args.len() as u32, 2, context.pos))
+		}
+	}
+}
+
+
+#[derive(Trace, Finalize)]
+struct Split;
+
+impl NativeFun for Split {
+	fn name(&self) -> &'static str { "std.path.split" }
+
+	fn call(&self, context: CallContext) -> Result<Value, Panic> {
+		match context.args() {
+			[ Value::String(ref string) ] => Ok(
+				as_path(string)
+					.components()
+					.map(|component| component.as_os_str().as_bytes().into())
+					.collect::<Vec<Value>>()
+					.into()
+			),
+
+			[ other ] => Err(Panic::type_error(other.copy(), "string", context.pos)),
+			args => Err(Panic::invalid_args(args.len() as u32, 1, context.pos))
+		}
+	}
+}
+
+
+fn as_path(string: &Str) -> &Path {
+	Path::new(AsRef::<OsStr>::as_ref(string))
+}
+
+
+/// Convert an optional path component to a string, or nil.
+fn to_value(component: Option<&OsStr>) -> Value {
+	component
+		.map(|component| component.as_bytes().into())
+		.unwrap_or_default()
+}
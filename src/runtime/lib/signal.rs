@@ -0,0 +1,108 @@
+use std::convert::TryFrom;
+
+use gc::{Finalize, Trace};
+
+use crate::runtime::signal;
+use super::{
+	CallContext,
+	Error,
+	NativeFun,
+	RustFun,
+	Panic,
+	Value,
+};
+
+
+inventory::submit! { RustFun::from(On) }
+inventory::submit! { RustFun::from(Ignore) }
+inventory::submit! { RustFun::from(SendSignal) }
+
+
+/// Call a function when the given signal is delivered. The function is called with no
+/// arguments, between statements. Commands are spawned with the default action for the
+/// signal.
+#[derive(Trace, Finalize)]
+struct On;
+
+impl NativeFun for On {
+	fn name(&self) -> &'static str { "std.signal.on" }
+
+	fn call(&self, context: CallContext) -> Result<Value, Panic> {
+		match context.args() {
+			[ name, Value::Function(fun) ] => {
+				let number = signal::number(name)
+					.ok_or_else(|| Panic::value_error(name.copy(), "signal", context.pos.copy()))?;
+
+				signal::catch(number)
+					.map_err(|_| Panic::value_error(name.copy(), "catchable signal", context.pos.copy()))?;
+
+				let fun = fun.copy();
+				context.runtime.signal_handlers.insert(number, (fun, context.pos));
+
+				Ok(Value::default())
+			}
+
+			[ _, other ] => Err(Panic::type_error(other.copy(), "function", context.pos)),
+			args => Err(Panic::invalid_args(args.len() as u32, 2, context.pos))
+		}
+	}
+}
+
+
+/// Ignore the given signal, discarding its callback if any. Commands inherit ignored
+/// signals.
+#[derive(Trace, Finalize)]
+struct Ignore;
+
+impl NativeFun for Ignore {
+	fn name(&self) -> &'static str { "std.signal.ignore" }
+
+	fn call(&self, context: CallContext) -> Result<Value, Panic> {
+		match context.args() {
+			[ name ] => {
+				let number = signal::number(name)
+					.ok_or_else(|| Panic::value_error(name.copy(), "signal", context.pos.copy()))?;
+
+				signal::ignore(number)
+					.map_err(|_| Panic::value_error(name.copy(), "catchable signal", context.pos.copy()))?;
+
+				context.runtime.signal_handlers.remove(&number);
+
+				Ok(Value::default())
+			}
+
+			args => Err(Panic::invalid_args(args.len() as u32, 1, context.pos))
+		}
+	}
+}
+
+
+/// Send a signal to a process.
+#[derive(Trace, Finalize)]
+struct SendSignal;
+
+impl NativeFun for SendSignal {
+	fn name(&self) -> &'static str { "std.signal.send" }
+
+	fn call(&self, context: CallContext) -> Result<Value, Panic> {
+		match context.args() {
+			[ pid @ Value::Int(i), name ] => {
+				let number = signal::number(name)
+					.ok_or_else(|| Panic::value_error(name.copy(), "signal", context.pos.copy()))?;
+
+				let pid = libc::pid_t::try_from(*i)
+					.map_err(|_| Panic::value_error(pid.copy(), "valid pid", context.pos.copy()))?;
+
+				Ok(
+					match signal::send(pid, number) {
+						Ok(()) => Value::default(),
+						Err(error) => Error::new(error.to_string().into(), Value::Int(*i)).into(),
+					}
+				)
+			}
+
+			[ other, _ ] => Err(Panic::type_error(other.copy(), "int", context.pos)),
+			args => Err(Panic::invalid_args(args.len() as u32, 2, context.pos))
+		}
+	}
+}
@@ -0,0 +1,58 @@
+use std::time::{Duration, Instant};
+
+use gc::{Finalize, Trace};
+
+use super::{
+	CallContext,
+	NativeFun,
+	RustFun,
+	Panic,
+	Value,
+};
+
+
+inventory::submit!{ RustFun::from(Timeout) }
+
+/// Call a function, bounding the execution time of the command blocks executed during
+/// the call. Commands which exceed the deadline are killed, and their blocks evaluate
+/// to an error. Nested timeouts can only shorten the deadline.
+#[derive(Trace, Finalize)]
+struct Timeout;
+
+impl NativeFun for Timeout {
+	fn name(&self) -> &'static str { "std.timeout" }
+
+	fn call(&self, mut context: CallContext) -> Result<Value, Panic> {
+		let (seconds, fun) = match context.args() {
+			[ Value::Int(i), Value::Function(fun) ] if *i >= 0 => (*i as f64, fun.copy()),
+			[ Value::Float(f), Value::Function(fun) ] if f.0 >= 0.0 && f.0.is_finite() => (f.0, fun.copy()),
+
+			[ value @ Value::Int(_), Value::Function(_) ] | [ value @ Value::Float(_), Value::Function(_) ] =>
+				return Err(Panic::value_error(value.copy(), "positive number", context.pos)),
+
+			[ Value::Int(_), other ] | [ Value::Float(_), other ] =>
+				return Err(Panic::type_error(other.copy(), "function", context.pos)),
+			[ other, _ ] => return Err(Panic::type_error(other.copy(), "int or float", context.pos)),
+			args => return Err(Panic::invalid_args(args.len() as u32, 2, context.pos))
+		};
+
+		let deadline = Instant::now()
+			.checked_add(Duration::from_secs_f64(seconds));
+
+		let previous = context.runtime.deadline;
+		context.runtime.deadline = match (previous, deadline) {
+			(Some(previous), Some(deadline)) => Some(previous.min(deadline)),
+			(previous, deadline) => deadline.or(previous),
+		};
+
+		let result = context.call(
+			Value::default(),
+			&fun,
+			context.args_start + 2
+		);
+
+		context.runtime.deadline = previous;
+
+		result
+	}
+}
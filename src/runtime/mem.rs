@@ -131,6 +131,25 @@ impl Stack {
 	}
 
 
+	/// Add the given ammount of Nil valued slots to the bottom of the stack.
+	/// This is used to grow the root frame, and therefore must only be used when the stack
+	/// contains nothing but the root frame.
+	/// Returns StackOverflow if the size exceeds the maximum size.
+	pub fn extend_bottom(&mut self, slots: SlotIx) -> Result<(), StackOverflow> {
+		let new_size = self.len() + slots.0 as usize;
+
+		if new_size > self.max_size {
+			Err(StackOverflow)
+		} else {
+			self.slots.splice(
+				0..0,
+				std::iter::repeat_with(Slot::default).take(slots.0 as usize)
+			);
+			Ok(())
+		}
+	}
+
+
 	/// Remove the given ammount of elements from the top of the stack.
 	pub fn shrink(&mut self, slots: SlotIx) {
 		self.slots.truncate(self.len() - slots.0 as usize);
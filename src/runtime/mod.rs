@@ -4,17 +4,24 @@
 
 mod command;
 mod flow;
+mod int;
 mod lib;
 mod mem;
 mod panic;
+mod signal;
 mod source;
 pub mod value;
 #[cfg(test)]
 mod tests;
 
-use std::{collections::HashMap, ops::Deref};
+use std::{collections::HashMap, ops::Deref, time::Instant};
 
-use crate::symbol::{self, Symbol};
+use crate::{
+	diagnostics,
+	fmt::FmtString,
+	symbol::{self, Symbol},
+	syntax::SourceMap,
+};
 use super::semantic::program;
 use value::{
 	keys,
@@ -31,7 +38,7 @@ use value::{
 	Value,
 	Type,
 };
-pub use panic::Panic;
+pub use panic::{Panic, PanicDisplayContext};
 pub use source::SourcePos;
 use flow::Flow;
 use mem::Stack;
@@ -48,6 +55,18 @@ pub struct Runtime {
 	modules: HashMap<Symbol, Value>,
 	/// Command line arguments.
 	args: Value,
+	/// How to report static errors in imported modules.
+	diagnostics: diagnostics::Format,
+	/// Source code of the executed programs, for diagnostics.
+	sources: SourceMap,
+	/// Command aliases.
+	aliases: command::Aliases,
+	/// Deadline for command blocks, set by std.timeout.
+	deadline: Option<Instant>,
+	/// Asynchronous command blocks which are still running.
+	jobs: command::Jobs,
+	/// Callbacks for caught signals, set by std.signal.on.
+	signal_handlers: HashMap<libc::c_int, (Function, SourcePos)>,
 }
 
 
@@ -71,10 +90,40 @@ impl Runtime {
 			std: lib::new(),
 			modules: HashMap::new(),
 			args: args.into(),
+			diagnostics: diagnostics::Format::default(),
+			sources: SourceMap::default(),
+			aliases: command::Aliases::default(),
+			deadline: None,
+			jobs: command::Jobs::default(),
+			signal_handlers: HashMap::new(),
 		}
 	}
 
 
+	/// Set the format in which static errors in imported modules are reported.
+	pub fn set_diagnostics_format(&mut self, format: diagnostics::Format) {
+		self.diagnostics = format;
+	}
+
+
+	/// Set the source code of the executed programs, for diagnostics.
+	pub fn set_sources(&mut self, sources: SourceMap) {
+		self.sources = sources;
+	}
+
+
+	/// Get an immutable reference to the source code of the executed programs.
+	pub fn sources(&self) -> &SourceMap {
+		&self.sources
+	}
+
+
+	/// Get a mutable reference to the source code of the executed programs.
+	pub fn sources_mut(&mut self) -> &mut SourceMap {
+		&mut self.sources
+	}
+
+
 	/// Get an immutable reference to the symbol interner owned by this runtime.
 	pub fn interner(&self) -> &symbol::Interner {
 		&self.interner
@@ -118,6 +167,42 @@ impl Runtime {
 	}
 
 
+	/// Execute the given program in the context of an interactive session.
+	/// Global variables are kept alive after execution, and are visible to subsequent
+	/// programs of the session. Such programs must be produced by the same semantic
+	/// session.
+	pub fn eval_session(&mut self, program: &'static program::Program) -> Result<Value, Panic> {
+		let slots: mem::SlotIx = program.root_slots.into();
+		let root_slots = self.stack.len();
+
+		debug_assert!(self.arguments.is_empty());
+
+		// Global variables declared in this program.
+		if let Some(new_slots) = (slots.0 as usize).checked_sub(root_slots) {
+			self.stack
+				.extend_bottom(mem::SlotIx(new_slots as u32))
+				.map_err(|_| Panic::stack_overflow(SourcePos::file(program.source)))?;
+		}
+
+		// Stdlib.
+		if root_slots == 0 {
+			self.stack.store(mem::SlotIx(0), self.std.copy());
+		}
+
+		let result = self.eval_block(&program.statements);
+
+		// Make sure to leave only the root frame on panics.
+		self.arguments.clear();
+		let leftover = self.stack.len() - slots.0 as usize;
+		self.stack.shrink(mem::SlotIx(leftover as u32));
+
+		match result? {
+			Flow::Regular(value) => Ok(value),
+			flow => panic!("invalid flow in root state: {:#?}", flow)
+		}
+	}
+
+
 	/// Execute a block, returning the value of the last statement, or the corresponding
 	/// control flow if returns or breaks are reached.
 	fn eval_block(&mut self, block: &'static program::Block) -> Result<Flow, Panic> {
@@ -220,7 +305,7 @@ impl Runtime {
 			}
 
 			// Function.
-			program::Literal::Function { params, frame_info, body } => {
+			program::Literal::Function { params, defaults, variadic, frame_info, body } => {
 				let context = frame_info
 					.captures
 					.iter()
@@ -234,7 +319,15 @@ impl Runtime {
 
 				Ok(
 					Flow::Regular(
-						HushFun::new(*params, frame_info, body, context, pos.into()).into()
+						HushFun::new(
+							*params,
+							defaults,
+							*variadic,
+							frame_info,
+							body,
+							context,
+							pos.into()
+						).into()
 					)
 				)
 			},
@@ -331,36 +424,43 @@ impl Runtime {
 				Ok((value, pos, Value::default()))
 			}
 
-			// Access.
-			program::Expr::Access { object, field, pos } => {
+			// Match.
+			program::Expr::Match { value, arms, pos } => {
+				let match_pos = *pos;
 				let pos = pos.into();
 
-				let (obj, obj_pos) = regular_expr!(object, pos);
-				let (field, field_pos) = regular_expr!(field, pos);
+				let (value, _) = regular_expr!(value, pos);
+
+				for arm in arms.iter() {
+					if !self.match_pattern(&arm.pattern, &value, match_pos)? {
+						continue;
+					}
 
-				let value = match (&obj, field) {
-					(Value::Dict(ref dict), field) => dict
-						.get(&field)
-						.map_err(|_| Panic::index_out_of_bounds(field, field_pos)),
+					if let Some(guard) = &arm.guard {
+						match self.eval_expr(guard)? {
+							(Flow::Regular(Value::Bool(true)), _, _) => (),
+							(Flow::Regular(Value::Bool(false)), _, _) => continue,
+							(Flow::Regular(value), pos, _) => return Err(Panic::invalid_condition(value, pos)),
+							(flow, _, _) => return Ok((flow, pos, Value::default()))
+						}
+					}
 
-					(Value::Array(ref array), Value::Int(ix)) => array
-						.index(ix)
-						.map_err(|_| Panic::index_out_of_bounds(Value::Int(ix), field_pos)),
+					let value = self.eval_block(&arm.block)?;
 
-					(Value::Array(_), field) => Err(Panic::type_error(field, "int", field_pos)),
+					return Ok((value, pos, Value::default()));
+				}
 
-					(Value::String(ref string), Value::Int(ix)) => string
-						.index(ix)
-						.map_err(|_| Panic::index_out_of_bounds(Value::Int(ix), field_pos)),
+				Ok((Flow::Regular(Value::Nil), pos, Value::default()))
+			}
 
-					(Value::String(_), field) => Err(Panic::type_error(field, "int", field_pos)),
+			// Access.
+			program::Expr::Access { object, field, pos } => {
+				let pos = pos.into();
 
-					(Value::Error(ref error), field) => error
-						.get(&field)
-						.map_err(|_| Panic::index_out_of_bounds(field, field_pos)),
+				let (obj, obj_pos) = regular_expr!(object, pos);
+				let (field, field_pos) = regular_expr!(field, pos);
 
-					(_, _) => return Err(Panic::type_error(obj, "string, array, dict or error", obj_pos)),
-				}?;
+				let value = Self::access(&obj, obj_pos, field, field_pos)?;
 
 				Ok((Flow::Regular(value), pos, obj))
 			}
@@ -403,6 +503,104 @@ impl Runtime {
 				let value = self.eval_command_block(block, pos.into())?;
 				Ok((Flow::Regular(value), pos.into(), Value::default()))
 			}
+
+			// Interpolation.
+			program::Expr::Interpolation { parts, pos } => {
+				let pos = pos.into();
+
+				let mut string = Vec::new();
+
+				for part in parts.iter() {
+					let (value, _) = regular_expr!(part, pos);
+
+					match value {
+						Value::String(ref part) => string.extend_from_slice(part.as_bytes()),
+						value => string.extend_from_slice(value.fmt_string(&self.interner).as_bytes()),
+					}
+				}
+
+				Ok((Flow::Regular(string.into_boxed_slice().into()), pos, Value::default()))
+			}
+		}
+	}
+
+
+	/// Check if a value matches the pattern, storing the bound sub-values in their slots.
+	/// Bindings may be partially stored when the match fails.
+	fn match_pattern(
+		&mut self,
+		pattern: &'static program::Pattern,
+		value: &Value,
+		pos: program::SourcePos,
+	) -> Result<bool, Panic> {
+		match pattern {
+			// Wildcard.
+			program::Pattern::Wildcard => Ok(true),
+
+			// Literal.
+			program::Pattern::Literal(literal) => match self.eval_literal(literal, pos)? {
+				Flow::Regular(literal) => Ok(&literal == value),
+				_ => unreachable!("basic literals have regular flow"),
+			},
+
+			// Binding.
+			program::Pattern::Binding { slot_ix } => {
+				self.stack.store(slot_ix.into(), value.copy());
+				Ok(true)
+			}
+
+			// Type.
+			program::Pattern::Type { pattern, type_name } => {
+				if value.get_type() == *type_name {
+					self.match_pattern(pattern, value, pos)
+				} else {
+					Ok(false)
+				}
+			}
+
+			// Array.
+			program::Pattern::Array(patterns) => {
+				let items = match value {
+					Value::Array(array) if array.len() == patterns.len() as i64 => array
+						.borrow()
+						.iter()
+						.map(Value::copy)
+						.collect::<Vec<_>>(),
+
+					_ => return Ok(false),
+				};
+
+				for (pattern, item) in patterns.iter().zip(items.iter()) {
+					if !self.match_pattern(pattern, item, pos)? {
+						return Ok(false);
+					}
+				}
+
+				Ok(true)
+			}
+
+			// Dict.
+			program::Pattern::Dict(patterns) => {
+				let dict = match value {
+					Value::Dict(dict) => dict.copy(),
+					_ => return Ok(false),
+				};
+
+				for (key, pattern) in patterns.iter() {
+					let key: Value = key.as_ref().into();
+
+					let item = match dict.get(&key) {
+						Ok(item) => item,
+						Err(_) => return Ok(false),
+					};
+
+					if !self.match_pattern(pattern, &item, pos)? {
+						return Ok(false);
+					}
+				}
+
+				Ok(true)
+			}
 		}
 	}
 
@@ -422,6 +620,11 @@ impl Runtime {
 	where
 		F: FnOnce(&mut Self),
 	{
+		// Statement boundaries are safe points for signal callbacks.
+		if signal::is_pending() {
+			self.dispatch_signals()?;
+		}
+
 		match statement {
 			// Assign.
 			program::Statement::Assign { left, right } => {
@@ -444,26 +647,61 @@ impl Runtime {
 							(flow, _, _) => return Ok(flow),
 						};
 
-						match (obj, field) {
-							// Note that strings are immutable.
+						Self::assign_field(obj, obj_pos, field, field_pos, value, pos.into())?;
+					}
+				}
+
+				Ok(Flow::Regular(Value::default()))
+			}
+
+			// Destructure.
+			program::Statement::Destructure { pattern, right, pos } => {
+				let value = match self.eval_expr(right)?.0 {
+					Flow::Regular(value) => value,
+					flow => return Ok(flow),
+				};
+
+				if self.match_pattern(pattern, &value, *pos)? {
+					Ok(Flow::Regular(Value::default()))
+				} else {
+					Err(Panic::pattern_mismatch(value, pos.into()))
+				}
+			}
+
+			// Compound assign.
+			program::Statement::CompoundAssign { left, op, right, pos } => {
+				let pos = pos.into();
 
-							(Value::Dict(ref dict), field) => dict.insert(field, value),
+				macro_rules! regular_expr {
+					($expr: expr) => {
+						match self.eval_expr($expr)? {
+							(Flow::Regular(value), pos, _) => (value, pos),
+							(flow, _, _) => return Ok(flow),
+						}
+					}
+				}
 
-							(Value::Array(ref array), Value::Int(ix)) if ix >= array.len() => return Err(
-								Panic::index_out_of_bounds(Value::Int(ix), field_pos)
-							),
+				match left {
+					program::Lvalue::Identifier { slot_ix, pos: left_pos } => {
+						let value = self.stack.fetch(slot_ix.into());
+						let (right, right_pos) = regular_expr!(right);
 
-							(Value::Array(ref array), Value::Int(ix)) => array
-								.deref()
-								.set(ix, value)
-								.map_err(|_| Panic::index_out_of_bounds(Value::Int(ix), pos.into()))?,
+						let value = self.compound_op(value, left_pos.into(), op, &pos, right, right_pos)?;
 
-							(Value::Array(_), field) => return Err(Panic::type_error(field, "int", field_pos)),
+						self.stack.store(slot_ix.into(), value);
+					}
 
-							(Value::Error(_), field) => return Err(Panic::assign_to_readonly_field(field, field_pos)),
+					program::Lvalue::Access { object, field, pos: left_pos } => {
+						// The object and the field must be evaluated only once.
+						let (obj, obj_pos) = regular_expr!(object);
+						let (field, field_pos) = regular_expr!(field);
 
-							(obj, _) => return Err(Panic::type_error(obj, "array, dict or error", obj_pos)),
-						};
+						let value = Self::access(&obj, obj_pos.copy(), field.copy(), field_pos.copy())?;
+						let (right, right_pos) = regular_expr!(right);
+
+						let value = self.compound_op(value, left_pos.into(), op, &pos, right, right_pos)?;
+
+						Self::assign_field(obj, obj_pos, field, field_pos, value, left_pos.into())?;
 					}
 				}
 
@@ -481,6 +719,9 @@ impl Runtime {
 			// Break.
 			program::Statement::Break => Ok(Flow::Break),
 
+			// Continue.
+			program::Statement::Continue => Ok(Flow::Continue),
+
 			// While.
 			program::Statement::While { condition, block } => {
 				loop {
@@ -495,7 +736,7 @@ impl Runtime {
 					}
 
 					match self.eval_block(block)? {
-						Flow::Regular(_) => (),
+						Flow::Regular(_) | Flow::Continue => (),
 						flow @ Flow::Return(_) => return Ok(flow),
 						Flow::Break => break,
 					}
@@ -505,69 +746,112 @@ impl Runtime {
 			}
 
 			// For.
-			program::Statement::For { slot_ix, expr, block } => {
-				let slot_ix: mem::SlotIx = slot_ix.into();
-
+			program::Statement::For { pattern, expr, block, pos: for_pos } => {
 				let (iter, pos) = match self.eval_expr(expr)? {
 					(Flow::Regular(Value::Function(ref iter)), pos, _) => (iter.copy(), pos),
 					(Flow::Regular(value), pos, _) => return Err(Panic::type_error(value, "function", pos)),
 					(flow, _, _) => return Ok(flow)
 				};
 
-				loop {
-					// While evaluating arguments, we may need to call other functions, so we must
-					// keep track of when our arguments start.
-					let args_start = self.arguments.len();
-					match self.call(Value::default(), &iter, args_start, pos.copy())? {
-						Value::Dict(ref dict) => {
-							let finished = keys::FINISHED.with(
-								|finished| dict
-									.get(finished)
-									.map_err(|_| Panic::index_out_of_bounds(finished.copy(), pos.copy()))
-							)?;
+				let mut finished = false;
+				let flow = self.eval_for(pattern, &iter, block, pos, *for_pos, &mut finished);
+
+				// Iterators left before they are finished, be it by a break, a return or a panic,
+				// may release their resources.
+				if !finished {
+					iter.close();
+				}
 
-							match finished {
-								Value::Bool(false) => {
-									let value = keys::VALUE.with(
-										|value| dict
-											.get(value)
-											.map_err(|_| Panic::index_out_of_bounds(value.copy(), pos.copy()))
-									)?;
+				flow
+			}
 
-									self.stack.store(slot_ix.copy(), value);
-								},
+			// Expr.
+			program::Statement::Expr(expr) => self
+				.eval_tail_expr(expr, tail_call)
+				.map(|(flow, _, _)| flow)
+		}
+	}
 
-								Value::Bool(true) => break,
 
-								other => return Err(Panic::type_error(other, "bool", pos))
-							}
+	/// Execute a for loop over the given iterator. The finished flag is set when the
+	/// iterator is exhausted.
+	fn eval_for(
+		&mut self,
+		pattern: &'static program::Pattern,
+		iter: &Function,
+		block: &'static program::Block,
+		pos: SourcePos,
+		for_pos: program::SourcePos,
+		finished: &mut bool,
+	) -> Result<Flow, Panic> {
+		loop {
+			// While evaluating arguments, we may need to call other functions, so we must
+			// keep track of when our arguments start.
+			let args_start = self.arguments.len();
+			match self.call(Value::default(), iter, args_start, pos.copy())? {
+				Value::Dict(ref dict) => {
+					let done = keys::FINISHED.with(
+						|finished| dict
+							.get(finished)
+							.map_err(|_| Panic::index_out_of_bounds(finished.copy(), pos.copy()))
+					)?;
+
+					match done {
+						Value::Bool(false) => {
+							let value = keys::VALUE.with(
+								|value| dict
+									.get(value)
+									.map_err(|_| Panic::index_out_of_bounds(value.copy(), pos.copy()))
+							)?;
 
-							Value::Nil
+							if !self.match_pattern(pattern, &value, for_pos)? {
+								return Err(Panic::pattern_mismatch(value, for_pos.into()));
+							}
 						},
 
-						other => return Err(Panic::type_error(other, "dict", pos)),
-					};
+						Value::Bool(true) => {
+							*finished = true;
+							break;
+						}
 
-					match self.eval_block(block)? {
-						Flow::Regular(_) => (),
-						flow @ Flow::Return(_) => return Ok(flow),
-						Flow::Break => break,
+						other => return Err(Panic::type_error(other, "bool", pos))
 					}
-				}
+				},
 
-				Ok(Flow::Regular(Value::default()))
+				other => return Err(Panic::type_error(other, "dict", pos)),
+			};
+
+			match self.eval_block(block)? {
+				Flow::Regular(_) | Flow::Continue => (),
+				flow @ Flow::Return(_) => return Ok(flow),
+				Flow::Break => break,
 			}
+		}
 
-			// Expr.
-			program::Statement::Expr(expr) => self
-				.eval_tail_expr(expr, tail_call)
-				.map(|(flow, _, _)| flow)
+		Ok(Flow::Regular(Value::default()))
+	}
+
+
+	/// Call the callbacks for the signals delivered since the last dispatch.
+	fn dispatch_signals(&mut self) -> Result<(), Panic> {
+		for signal in signal::take_pending() {
+			let handler = self.signal_handlers
+				.get(&signal)
+				.map(|(handler, pos)| (handler.copy(), pos.copy()));
+
+			if let Some((handler, pos)) = handler {
+				let args_start = self.arguments.len();
+				self.call(Value::default(), &handler, args_start, pos)?;
+			}
 		}
+
+		Ok(())
 	}
 
 
 	/// Call the given function.
 	/// The arguments are expected to be on the self.arguments vector.
+	/// If the call panics, the call frame is recorded in the panic's backtrace.
 	fn call(
 		&mut self,
 		obj: Value,
@@ -575,15 +859,39 @@ impl Runtime {
 		args_start: usize,
 		pos: SourcePos,
 	) -> Result<Value, Panic> {
+		self
+			.call_function(obj, function, args_start, pos.copy())
+			.map_err(
+				|mut panic| {
+					panic.unwind(function.copy(), pos);
+					panic
+				}
+			)
+	}
+
+
+	/// Call the given function, without recording the call frame.
+	fn call_function(
+		&mut self,
+		obj: Value,
+		function: &Function,
+		args_start: usize,
+		pos: SourcePos,
+	) -> Result<Value, Panic> {
 
 		let value = match function {
-			Function::Hush(HushFun { params, frame_info, body, context, .. }) => {
+			Function::Hush(HushFun { params, defaults, variadic, frame_info, body, context, .. }) => {
 				let args_count = (self.arguments.len() - args_start) as u32;
+				let required = *params - defaults.len() as u32;
 
 				// Make sure we clean the arguments vector even when early returning.
-				let arguments = self.arguments.drain(args_start..);
+				let mut arguments = self.arguments.drain(args_start..);
+
+				if args_count < required {
+					return Err(Panic::invalid_args(args_count, required, pos));
+				}
 
-				if args_count != *params {
+				if args_count > *params && !*variadic {
 					return Err(Panic::invalid_args(args_count, *params, pos));
 				}
 
@@ -592,10 +900,18 @@ impl Runtime {
 					.map_err(|_| Panic::stack_overflow(pos))?;
 
 				// Place arguments
-				for (ix, value) in arguments.enumerate() {
+				for (ix, value) in arguments.by_ref().take(*params as usize).enumerate() {
 					self.stack.store(mem::SlotIx(ix as u32), value);
 				}
 
+				// Place extra arguments in the rest parameter, which follows the other parameters.
+				if *variadic {
+					let rest: Vec<Value> = arguments.collect();
+					self.stack.store(mem::SlotIx(*params), Array::new(rest).into());
+				} else {
+					drop(arguments);
+				}
+
 				// Place captured variables.
 				for (value, slot_ix) in context.iter().cloned() {
 					self.stack.place(slot_ix, value);
@@ -606,6 +922,31 @@ impl Runtime {
 					self.stack.store(slot_ix.into(), obj);
 				}
 
+				// Evaluate the default values of the missing arguments, which may refer to the
+				// previous parameters.
+				let defaults: &'static [program::Expr] = defaults;
+
+				for ix in args_count.min(*params) .. *params {
+					let default = &defaults[(ix - required) as usize];
+
+					let early_return = match self.eval_expr(default) {
+						Ok((Flow::Regular(value), _, _)) => {
+							self.stack.store(mem::SlotIx(ix), value);
+							None
+						}
+
+						Ok((Flow::Return(value), _, _)) => Some(Ok(value)),
+						Ok((Flow::Break, _, _)) => panic!("break outside loop"),
+						Ok((Flow::Continue, _, _)) => panic!("continue outside loop"),
+						Err(panic) => Some(Err(panic)),
+					};
+
+					if let Some(result) = early_return {
+						self.stack.shrink(slots);
+						return result;
+					}
+				}
+
 				let mut shrinked = false;
 
 				let result = self.eval_tail_block(
@@ -627,6 +968,7 @@ impl Runtime {
 					Flow::Regular(value) => value,
 					Flow::Return(value) => value,
 					Flow::Break => panic!("break outside loop"),
+					Flow::Continue => panic!("continue outside loop"),
 				}
 			}
 
@@ -714,7 +1056,8 @@ impl Runtime {
 				(left, _) => return Err(Panic::type_error(left, "bool", left_pos)),
 			}
 
-			Plus | Minus | Times | Div | Mod => {
+			Plus | Minus | Times | Div | IntDiv | Mod | Pow
+				| BitAnd | BitOr | BitXor | ShiftLeft | ShiftRight => {
 				let (right, right_pos) = regular_expr!(right);
 
 				self.arithmetic_op(left, left_pos, op, pos, right, right_pos)?
@@ -732,21 +1075,7 @@ impl Runtime {
 			Concat => {
 				let (right, right_pos) = regular_expr!(right);
 
-				match (left, right) {
-					(Value::String(ref str1), Value::String(ref str2)) => {
-						let string =
-							[
-								AsRef::<[u8]>::as_ref(str1),
-								AsRef::<[u8]>::as_ref(str2),
-							]
-							.concat::<u8>();
-
-						string.into_boxed_slice().into()
-					}
-
-					(Value::String(_), right) => return Err(Panic::type_error(right, "string", right_pos)),
-					(left, _) => return Err(Panic::type_error(left, "string", left_pos)),
-				}
+				Self::concat_op(left, left_pos, right, right_pos)?
 			}
 		};
 
@@ -754,8 +1083,124 @@ impl Runtime {
 	}
 
 
+	/// Execute the binary operator of a compound assignment, with already evaluated
+	/// operands.
+	/// Panics if op is not arithmetic, bitwise or concat (++).
+	fn compound_op(
+		&mut self,
+		left: Value,
+		left_pos: SourcePos,
+		op: &'static program::BinaryOp,
+		pos: &SourcePos,
+		right: Value,
+		right_pos: SourcePos,
+	) -> Result<Value, Panic> {
+		use program::BinaryOp::*;
+
+		match op {
+			Plus | Minus | Times | Div | IntDiv | Mod | Pow
+				| BitAnd | BitOr | BitXor | ShiftLeft | ShiftRight =>
+				self.arithmetic_op(left, left_pos, op, pos, right, right_pos),
+			Concat => Self::concat_op(left, left_pos, right, right_pos),
+			_ => unreachable!("operator is not compound assignable"),
+		}
+	}
+
+
+	/// Execute a concat operator expression.
+	fn concat_op(
+		left: Value,
+		left_pos: SourcePos,
+		right: Value,
+		right_pos: SourcePos,
+	) -> Result<Value, Panic> {
+		match (left, right) {
+			(Value::String(ref str1), Value::String(ref str2)) => {
+				let string =
+					[
+						AsRef::<[u8]>::as_ref(str1),
+						AsRef::<[u8]>::as_ref(str2),
+					]
+					.concat::<u8>();
+
+				Ok(string.into_boxed_slice().into())
+			}
+
+			(Value::String(_), right) => Err(Panic::type_error(right, "string", right_pos)),
+			(left, _) => Err(Panic::type_error(left, "string", left_pos)),
+		}
+	}
+
+
+	/// Get the value of a field of the given object.
+	fn access(
+		obj: &Value,
+		obj_pos: SourcePos,
+		field: Value,
+		field_pos: SourcePos,
+	) -> Result<Value, Panic> {
+		match (obj, field) {
+			(Value::Dict(ref dict), field) => dict
+				.get(&field)
+				.map_err(|_| Panic::index_out_of_bounds(field, field_pos)),
+
+			(Value::Array(ref array), Value::Int(ix)) => array
+				.index(ix)
+				.map_err(|_| Panic::index_out_of_bounds(Value::Int(ix), field_pos)),
+
+			(Value::Array(_), field) => Err(Panic::type_error(field, "int", field_pos)),
+
+			(Value::String(ref string), Value::Int(ix)) => string
+				.index(ix)
+				.map_err(|_| Panic::index_out_of_bounds(Value::Int(ix), field_pos)),
+
+			(Value::String(_), field) => Err(Panic::type_error(field, "int", field_pos)),
+
+			(Value::Error(ref error), field) => error
+				.get(&field)
+				.map_err(|_| Panic::index_out_of_bounds(field, field_pos)),
+
+			(_, _) => Err(Panic::type_error(obj.copy(), "string, array, dict or error", obj_pos)),
+		}
+	}
+
+
+	/// Assign a value to a field of the given object.
+	fn assign_field(
+		obj: Value,
+		obj_pos: SourcePos,
+		field: Value,
+		field_pos: SourcePos,
+		value: Value,
+		pos: SourcePos,
+	) -> Result<(), Panic> {
+		match (obj, field) {
+			// Note that strings are immutable.
+
+			(Value::Dict(ref dict), field) => dict.insert(field, value),
+
+			(Value::Array(ref array), Value::Int(ix)) if ix >= array.len() => return Err(
+				Panic::index_out_of_bounds(Value::Int(ix), field_pos)
+			),
+
+			(Value::Array(ref array), Value::Int(ix)) => array
+				.deref()
+				.set(ix, value)
+				.map_err(|_| Panic::index_out_of_bounds(Value::Int(ix), pos))?,
+
+			(Value::Array(_), field) => return Err(Panic::type_error(field, "int", field_pos)),
+
+			(Value::Error(_), field) => return Err(Panic::assign_to_readonly_field(field, field_pos)),
+
+			(obj, _) => return Err(Panic::type_error(obj, "array, dict or error", obj_pos)),
+		};
+
+		Ok(())
+	}
+
+
 	/// Execute a binary arithmetic operator expression.
-	/// Panics if op is not arithmetic (+, -, *, /, %).
+	/// Panics if op is not arithmetic (+, -, *, /, //, %, **) or bitwise (&, |, ^, <<, >>).
 	fn arithmetic_op(
 		&mut self,
 		left: Value,
@@ -769,11 +1214,11 @@ impl Runtime {
 		use std::ops::{Add, Sub, Mul, Div, Rem};
 
 		macro_rules! arith_operator {
-			($op_float: expr, $op_int: ident, $err_int: expr) => {
+			($op_float: expr, $op_int: expr, $err_int: expr) => {
 				match (left, right) {
 					// int . int
 					(Value::Int(int1), Value::Int(int2)) => {
-						let val = int1.$op_int(int2).ok_or($err_int)?;
+						let val = $op_int(int1, int2).ok_or($err_int)?;
 						Ok(Value::Int(val))
 					},
 
@@ -795,37 +1240,99 @@ impl Runtime {
 			}
 		}
 
+		macro_rules! int_operator {
+			($op_int: expr, $err_int: expr) => {
+				match (left, right) {
+					// int . int
+					(Value::Int(int1), Value::Int(int2)) => {
+						let val = $op_int(int1, int2).ok_or($err_int)?;
+						Ok(Value::Int(val))
+					},
+
+					// int . ?
+					(Value::Int(_), right) => Err(Panic::type_error(right, "int", right_pos)),
+
+					// ? . ?
+					(left, _) => Err(Panic::type_error(left, "int", left_pos)),
+				}
+			}
+		}
+
+		// Exponents and shift amounts must not be negative.
+		if let (Pow | ShiftLeft | ShiftRight, Value::Int(_), Value::Int(int)) = (op, &left, &right) {
+			if *int < 0 {
+				return Err(Panic::value_error(right, "non-negative int", right_pos));
+			}
+		}
+
 		match op {
 			Plus => arith_operator!(
 				Add::add,
-				checked_add,
+				i64::checked_add,
 				Panic::integer_overflow(pos.copy())
 			),
 
 			Minus => arith_operator!(
 				Sub::sub,
-				checked_sub,
+				i64::checked_sub,
 				Panic::integer_overflow(pos.copy())
 			),
 
 			Times => arith_operator!(
 				Mul::mul,
-				checked_mul,
+				i64::checked_mul,
 				Panic::integer_overflow(pos.copy())
 			),
 
 			Div => arith_operator!(
 				Div::div,
-				checked_div,
+				i64::checked_div,
+				Panic::division_by_zero(pos.copy()) // TODO: this can be caused by overflow too.
+			),
+
+			IntDiv => arith_operator!(
+				|float1: Float, float2: Float| Float::from((float1.0 / float2.0).floor()),
+				int::checked_floor_div,
 				Panic::division_by_zero(pos.copy()) // TODO: this can be caused by overflow too.
 			),
 
 			Mod => arith_operator!(
 				Rem::rem,
-				checked_rem,
+				i64::checked_rem,
 				Panic::division_by_zero(pos.copy()) // TODO: this can be caused by overflow too.
 			),
 
+			Pow => arith_operator!(
+				|float1: Float, float2: Float| Float::from(float1.0.powf(float2.0)),
+				int::checked_pow,
+				Panic::integer_overflow(pos.copy())
+			),
+
+			BitAnd => int_operator!(
+				|int1: i64, int2| Some(int1 & int2),
+				Panic::integer_overflow(pos.copy())
+			),
+
+			BitOr => int_operator!(
+				|int1: i64, int2| Some(int1 | int2),
+				Panic::integer_overflow(pos.copy())
+			),
+
+			BitXor => int_operator!(
+				|int1: i64, int2| Some(int1 ^ int2),
+				Panic::integer_overflow(pos.copy())
+			),
+
+			ShiftLeft => int_operator!(
+				int::checked_shl,
+				Panic::integer_overflow(pos.copy())
+			),
+
+			ShiftRight => int_operator!(
+				int::shr,
+				Panic::integer_overflow(pos.copy())
+			),
+
 			_ => unreachable!("operator is not arithmetic"),
 		}
 	}
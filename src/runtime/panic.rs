@@ -2,16 +2,16 @@ use std::{borrow::Cow, io, ffi::OsString};
 
 use crate::{
 	fmt::{self, Display},
-	io::FileDescriptor,
 	term::color,
 	symbol::{self, Symbol},
+	syntax::{Snippet, SourceMap},
 };
-use super::{Value, SourcePos};
+use super::{Function, SourcePos, Value};
 
 
-/// A panic is an irrecoverable error in Hush.
+/// The cause of a panic.
 #[derive(Debug)]
-pub enum Panic {
+pub enum PanicKind {
 	/// Attempt to increase the stack past it's maximum size.
 	StackOverflow { pos: SourcePos },
 	/// Integer overflow.
@@ -41,6 +41,11 @@ pub enum Panic {
 		value: Value,
 		pos: SourcePos,
 	},
+	/// Value does not match the destructuring pattern.
+	PatternMismatch {
+		value: Value,
+		pos: SourcePos,
+	},
 	/// Unexpected type.
 	TypeError {
 		value: Value,
@@ -69,11 +74,6 @@ pub enum Panic {
 		error: io::Error,
 		pos: SourcePos,
 	},
-	/// Redirection of the given file descriptor is currently unsupported.
-	UnsupportedFileDescriptor {
-		fd: FileDescriptor,
-		pos: SourcePos,
-	},
 	/// Currently, Hush requires patterns to be valid UTF-8.
 	InvalidPattern {
 		pattern: OsString,
@@ -96,58 +96,132 @@ pub enum Panic {
 }
 
 
+impl PanicKind {
+	/// Display only the panic message, without the position.
+	pub fn message(&self) -> Message<'_> {
+		Message(self)
+	}
+
+
+	/// The position where the panic occurred.
+	pub fn pos(&self) -> &SourcePos {
+		match self {
+			Self::StackOverflow { pos, .. }
+			| Self::IntegerOverflow { pos, .. }
+			| Self::DivisionByZero { pos, .. }
+			| Self::IndexOutOfBounds { pos, .. }
+			| Self::EmptyCollection { pos, .. }
+			| Self::InvalidCall { pos, .. }
+			| Self::InvalidArgs { pos, .. }
+			| Self::InvalidCondition { pos, .. }
+			| Self::PatternMismatch { pos, .. }
+			| Self::TypeError { pos, .. }
+			| Self::ValueError { pos, .. }
+			| Self::AssignToReadonlyField { pos, .. }
+			| Self::InvalidCommandArgs { pos, .. }
+			| Self::Io { pos, .. }
+			| Self::InvalidPattern { pos, .. }
+			| Self::AssertionFailed { pos, .. }
+			| Self::ImportFailed { pos, .. }
+			| Self::InvalidJoin { pos, .. }
+			| Self::User { pos, .. } => pos,
+		}
+	}
+}
+
+
+/// A function call the panic has unwound through.
+#[derive(Debug)]
+pub struct CallFrame {
+	/// The called function.
+	pub function: Function,
+	/// Where the function was called from.
+	pub call_site: SourcePos,
+}
+
+
+/// A panic is an irrecoverable error in Hush.
+#[derive(Debug)]
+pub struct Panic {
+	/// The cause of the panic. Boxed to keep results small.
+	pub kind: Box<PanicKind>,
+	/// The function calls the panic has unwound through, from the innermost to the
+	/// outermost.
+	pub backtrace: Vec<CallFrame>,
+}
+
+
+impl From<PanicKind> for Panic {
+	fn from(kind: PanicKind) -> Self {
+		Self { kind: Box::new(kind), backtrace: Vec::new() }
+	}
+}
+
+
 impl Panic {
+	/// Record a function call the panic is unwinding through.
+	pub fn unwind(&mut self, function: Function, call_site: SourcePos) {
+		self.backtrace.push(CallFrame { function, call_site });
+	}
+
+
 	/// Attempt to increase the stack past it's maximum size.
 	pub fn stack_overflow(pos: SourcePos) -> Self {
-		Self::StackOverflow { pos }
+		PanicKind::StackOverflow { pos }.into()
 	}
 
 
 	/// Assertion failed.
 	pub fn assertion_failed(pos: SourcePos) -> Self {
-		Self::AssertionFailed { pos }
+		PanicKind::AssertionFailed { pos }.into()
 	}
 
 
 	/// Integer division by zero.
 	pub fn integer_overflow(pos: SourcePos) -> Self {
-		Self::IntegerOverflow { pos }
+		PanicKind::IntegerOverflow { pos }.into()
 	}
 
 
 	/// Integer division by zero.
 	pub fn division_by_zero(pos: SourcePos) -> Self {
-		Self::DivisionByZero { pos }
+		PanicKind::DivisionByZero { pos }.into()
 	}
 
 
 	/// Array or dict index out of bounds.
 	pub fn index_out_of_bounds(index: Value, pos: SourcePos) -> Self {
-		Self::IndexOutOfBounds { index, pos }
+		PanicKind::IndexOutOfBounds { index, pos }.into()
 	}
 
 
 	/// Attempt to pop from empty collection.
 	pub fn empty_collection(pos: SourcePos) -> Self {
-		Self::EmptyCollection { pos }
+		PanicKind::EmptyCollection { pos }.into()
 	}
 
 
 	/// Attempt to call a non-function value.
 	pub fn invalid_call(function: Value, pos: SourcePos) -> Self {
-		Self::InvalidCall { function, pos }
+		PanicKind::InvalidCall { function, pos }.into()
 	}
 
 
 	/// Ammount of supplied arguments in function call is different than expected.
 	pub fn invalid_args(supplied: u32, expected: u32, pos: SourcePos) -> Self {
-		Self::InvalidArgs { supplied, expected, pos }
+		PanicKind::InvalidArgs { supplied, expected, pos }.into()
 	}
 
 
 	/// Conditional expression is not a boolean.
 	pub fn invalid_condition(value: Value, pos: SourcePos) -> Self {
-		Self::InvalidCondition { value, pos }
+		PanicKind::InvalidCondition { value, pos }.into()
+	}
+
+
+	/// Value does not match the destructuring pattern.
+	pub fn pattern_mismatch(value: Value, pos: SourcePos) -> Self {
+		PanicKind::PatternMismatch { value, pos }.into()
 	}
 
 
@@ -156,11 +230,11 @@ impl Panic {
 	where
 		E: Into<Cow<'static, str>>,
 	{
-		Self::TypeError {
+		PanicKind::TypeError {
 			value,
 			expected: expected.into(),
 			pos,
-		}
+		}.into()
 	}
 
 
@@ -169,195 +243,184 @@ impl Panic {
 	where
 		E: Into<Cow<'static, str>>,
 	{
-		Self::ValueError {
+		PanicKind::ValueError {
 			value,
 			message: message.into(),
 			pos,
-		}
+		}.into()
 	}
 
 
 	/// Expansion resulted in zero or multiple items where a single item was expected.
 	pub fn invalid_command_args(object: &'static str, items: u32, pos: SourcePos) -> Self {
-		Self::InvalidCommandArgs { object, items, pos }
+		PanicKind::InvalidCommandArgs { object, items, pos }.into()
 	}
 
 
 	/// IO error.
 	pub fn io(error: io::Error, pos: SourcePos) -> Self {
-		Self::Io { error, pos }
+		PanicKind::Io { error, pos }.into()
 	}
 
 
-	/// Redirection of the given file descriptor is currently unsupported.
-	pub fn unsupported_fd(fd: FileDescriptor, pos: SourcePos) -> Self {
-		Self::UnsupportedFileDescriptor { fd, pos }
-	}
-
 	/// Currently, Hush requires patterns to be valid UTF-8.
 	pub fn invalid_pattern(pattern: OsString, pos: SourcePos) -> Self {
-		Self::InvalidPattern { pattern, pos }
+		PanicKind::InvalidPattern { pattern, pos }.into()
 	}
 
 
 	/// Attempt to assign a readonly field value.
 	pub fn assign_to_readonly_field(field: Value, pos: SourcePos) -> Self {
-		Self::AssignToReadonlyField { field, pos }
+		PanicKind::AssignToReadonlyField { field, pos }.into()
 	}
 
 	/// Failed to import module.
 	pub fn import_failed(path: Symbol, pos: SourcePos) -> Self {
-		Self::ImportFailed { path, pos }
+		PanicKind::ImportFailed { path, pos }.into()
 	}
 
 	/// Attempt to call <command>.join more than once.
 	pub fn invalid_join(pos: SourcePos) -> Self {
-		Self::InvalidJoin { pos }
+		PanicKind::InvalidJoin { pos }.into()
 	}
 
 	/// std.panic
 	pub fn user(context: Value, pos: SourcePos) -> Self {
-		Self::User { context, pos }
+		PanicKind::User { context, pos }.into()
 	}
 }
 
 
-impl<'a> Display<'a> for Panic {
+impl<'a> Display<'a> for PanicKind {
 	type Context = &'a symbol::Interner;
 
 	fn fmt(&self, f: &mut std::fmt::Formatter, context: Self::Context) -> std::fmt::Result {
-		let panic = color::Fg(color::Red, "Panic");
+		write!(
+			f,
+			"{} in {}: {}",
+			color::Fg(color::Red, "Panic"),
+			fmt::Show(self.pos(), context),
+			fmt::Show(Message(self), context)
+		)
+	}
+}
+
+
+/// Display only the panic message, without the position.
+#[derive(Debug, Clone, Copy)]
+pub struct Message<'a>(pub &'a PanicKind);
 
-		match self {
-			Self::StackOverflow { pos } =>
-				write!(f, "{} in {}: stack overflow", panic, fmt::Show(pos, context)),
 
-			Self::IntegerOverflow { pos } =>
-				write!(f, "{} in {}: integer overflow", panic, fmt::Show(pos, context)),
+impl<'a, 'b> Display<'a> for Message<'b> {
+	type Context = &'a symbol::Interner;
+
+	fn fmt(&self, f: &mut std::fmt::Formatter, context: Self::Context) -> std::fmt::Result {
+		match self.0 {
+			PanicKind::StackOverflow { .. } =>
+				write!(f, "stack overflow"),
 
-			Self::DivisionByZero { pos } =>
-				write!(f, "{} in {}: division by zero", panic, fmt::Show(pos, context)),
+			PanicKind::IntegerOverflow { .. } =>
+				write!(f, "integer overflow"),
 
-			Self::IndexOutOfBounds { index, pos } =>
+			PanicKind::DivisionByZero { .. } =>
+				write!(f, "division by zero"),
+
+			PanicKind::IndexOutOfBounds { index, .. } =>
 				write!(
 					f,
-					"{} in {}: index ({}) out of bounds",
-					panic,
-					fmt::Show(pos, context),
+					"index ({}) out of bounds",
 					color::Fg(color::Yellow, fmt::Show(index, context))
 				),
 
-			Self::EmptyCollection { pos } =>
-				write!(f, "{} in {}: collection is empty", panic, fmt::Show(pos, context)),
+			PanicKind::EmptyCollection { .. } =>
+				write!(f, "collection is empty"),
 
-			Self::InvalidCall { function, pos } =>
+			PanicKind::InvalidCall { function, .. } =>
 				write!(
 					f,
-					"{} in {}: attempt to call ({}), which is not a function",
-					panic,
-					fmt::Show(pos, context),
+					"attempt to call ({}), which is not a function",
 					color::Fg(color::Yellow, fmt::Show(function, context))
 				),
 
-			Self::InvalidArgs { supplied, expected, pos } =>
+			PanicKind::InvalidArgs { supplied, expected, .. } =>
 				write!(
 					f,
-					"{} in {}: incorrect amount of function parameters -- supplied {}, expected {}",
-					panic,
-					fmt::Show(pos, context),
+					"incorrect amount of function parameters -- supplied {}, expected {}",
 					supplied,
 					expected
 				),
 
-			Self::InvalidCondition { value, pos } =>
+			PanicKind::InvalidCondition { value, .. } =>
+				write!(
+					f,
+					"condition ({}) is not a boolean",
+					color::Fg(color::Yellow, fmt::Show(value, context))
+				),
+
+			PanicKind::PatternMismatch { value, .. } =>
 				write!(
 					f,
-					"{} in {}: condition ({}) is not a boolean",
-					panic,
-					fmt::Show(pos, context),
+					"value ({}) does not match the destructuring pattern",
 					color::Fg(color::Yellow, fmt::Show(value, context))
 				),
 
-			Self::TypeError { value, expected, pos } =>
+			PanicKind::TypeError { value, expected, .. } =>
 				write!(
 					f,
-					"{} in {}: value ({}) has unexpected type, expected {}",
-					panic,
-					fmt::Show(pos, context),
+					"value ({}) has unexpected type, expected {}",
 					color::Fg(color::Yellow, fmt::Show(value, context)),
 					expected,
 				),
 
-			Self::ValueError { value, message, pos } =>
+			PanicKind::ValueError { value, message, .. } =>
 				write!(
 					f,
-					"{} in {}: invalid value ({}), expected {}",
-					panic,
-					fmt::Show(pos, context),
+					"invalid value ({}), expected {}",
 					color::Fg(color::Yellow, fmt::Show(value, context)),
 					message,
 				),
 
-			Self::InvalidCommandArgs { object, items, pos } =>
+			PanicKind::InvalidCommandArgs { object, items, .. } =>
 				write!(
 					f,
-					"{} in {}: {} expansion resulted in {} items",
-					panic,
-					fmt::Show(pos, context),
+					"{} expansion resulted in {} items",
 					object,
 					items
 				),
 
-			Self::Io { error, pos } =>
-				write!(f, "{} in {}: {}", panic, fmt::Show(pos, context), error),
-
-			Self::UnsupportedFileDescriptor { fd, pos } =>
-				write!(
-					f,
-					"{} in {}: unsupported file descriptor ({})",
-					panic,
-					fmt::Show(pos, context),
-					color::Fg(color::Yellow, fd)
-				),
+			PanicKind::Io { error, .. } =>
+				write!(f, "{}", error),
 
-			Self::InvalidPattern { pattern, pos } =>
+			PanicKind::InvalidPattern { pattern, .. } =>
 				write!(
 					f,
-					"{} in {}: pattern ({:?}) has invalid UTF-8",
-					panic,
-					fmt::Show(pos, context),
+					"pattern ({:?}) has invalid UTF-8",
 					color::Fg(color::Yellow, pattern)
 				),
 
-			Self::AssignToReadonlyField { field, pos } => write!(
+			PanicKind::AssignToReadonlyField { field, .. } => write!(
 					f,
-					"{} in {}: attempt to assign field ({}), which is readonly",
-					panic,
-					fmt::Show(pos, context),
+					"attempt to assign field ({}), which is readonly",
 					color::Fg(color::Yellow, fmt::Show(field, context))
 				),
 
-			Self::AssertionFailed { pos } =>
-				write!(f, "{} in {}: assertion failed", panic, fmt::Show(pos, context)),
+			PanicKind::AssertionFailed { .. } =>
+				write!(f, "assertion failed"),
 
-			Self::ImportFailed { path, pos } =>
+			PanicKind::ImportFailed { path, .. } =>
 				write!(
 					f,
-					"{} in {}: failed to import module ({})",
-					panic,
-					fmt::Show(pos, context),
+					"failed to import module ({})",
 					color::Fg(color::Yellow, fmt::Show(path, context))
 				),
 
-			Self::InvalidJoin { pos } =>
-				write!(f, "{} in {}: attempt to call join more than once", panic, fmt::Show(pos, context)),
+			PanicKind::InvalidJoin { .. } =>
+				write!(f, "attempt to call join more than once"),
 
-			Self::User { context: value, pos } =>
+			PanicKind::User { context: value, .. } =>
 				write!(
 					f,
-					"{} in {}: std.panic({})",
-					panic,
-					fmt::Show(pos, context),
+					"std.panic({})",
 					color::Fg(color::Yellow, fmt::Show(value, context))
 				),
 		}
@@ -365,10 +428,68 @@ impl<'a> Display<'a> for Panic {
 }
 
 
+impl<'a> Display<'a> for CallFrame {
+	type Context = &'a symbol::Interner;
+
+	fn fmt(&self, f: &mut std::fmt::Formatter, context: Self::Context) -> std::fmt::Result {
+		write!(
+			f,
+			"{}, called from {}",
+			color::Fg(color::Yellow, fmt::Show(&self.function, context)),
+			fmt::Show(&self.call_site, context)
+		)
+	}
+}
+
+
+/// Context for displaying panics.
+#[derive(Debug, Copy, Clone)]
+pub struct PanicDisplayContext<'a> {
+	/// Symbol interner.
+	pub interner: &'a symbol::Interner,
+	/// Source code for snippets.
+	pub sources: &'a SourceMap,
+}
+
+
+impl<'a> Display<'a> for Panic {
+	type Context = PanicDisplayContext<'a>;
+
+	fn fmt(&self, f: &mut std::fmt::Formatter, context: Self::Context) -> std::fmt::Result {
+		// Deep recursion may produce huge backtraces, which are not useful to display.
+		const MAX_FRAMES: usize = 20;
+
+		write!(
+			f,
+			"{}{}",
+			fmt::Show(self.kind.as_ref(), context.interner),
+			fmt::Show(Snippet(self.kind.pos().into()), context.sources)
+		)?;
+
+		for frame in self.backtrace.iter().take(MAX_FRAMES) {
+			write!(f, "\n  in {}", fmt::Show(frame, context.interner))?;
+		}
+
+		if self.backtrace.len() > MAX_FRAMES {
+			write!(f, "\n  ... {} more calls", self.backtrace.len() - MAX_FRAMES)?;
+		}
+
+		Ok(())
+	}
+}
+
+
 /// We need this in order to be able to implement std::error::Error.
 impl std::fmt::Display for Panic {
 	fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
-		Display::fmt(self, f, &symbol::Interner::new())
+		Display::fmt(
+			self,
+			f,
+			PanicDisplayContext {
+				interner: &symbol::Interner::new(),
+				sources: &SourceMap::default(),
+			}
+		)
 	}
 }
 
@@ -0,0 +1,159 @@
+use std::{
+	convert::TryFrom,
+	io,
+	sync::atomic::{AtomicU64, Ordering},
+};
+
+use super::Value;
+
+
+/// Signals which have been delivered, but not yet dispatched to the script.
+static PENDING: AtomicU64 = AtomicU64::new(0);
+
+
+/// The OS signal handler. Only async-signal-safe operations may be performed here, so
+/// the signal is just recorded to be dispatched later, at a safe point.
+extern "C" fn record(signal: libc::c_int) {
+	PENDING.fetch_or(1 << signal, Ordering::SeqCst);
+}
+
+
+/// Record the given signal when delivered, instead of performing the default action.
+/// Caught signals are reset to the default action in spawned commands.
+pub fn catch(signal: libc::c_int) -> io::Result<()> {
+	set_disposition(signal, record as extern "C" fn(libc::c_int) as libc::sighandler_t)
+}
+
+
+/// Ignore the given signal. Ignored signals are inherited by spawned commands.
+pub fn ignore(signal: libc::c_int) -> io::Result<()> {
+	set_disposition(signal, libc::SIG_IGN)
+}
+
+
+/// Send a signal to the given process.
+pub fn send(pid: libc::pid_t, signal: libc::c_int) -> io::Result<()> {
+	if unsafe { libc::kill(pid, signal) } < 0 {
+		Err(io::Error::last_os_error())
+	} else {
+		Ok(())
+	}
+}
+
+
+/// The given process followed by its descendants, so that commands can be signaled along
+/// with the processes they spawned. The tree is read from procfs, and therefore only the
+/// process itself is returned in systems without it.
+pub fn process_tree(pid: libc::pid_t) -> Vec<libc::pid_t> {
+	let parents: Vec<(libc::pid_t, libc::pid_t)> = std::fs::read_dir("/proc")
+		.into_iter()
+		.flatten()
+		.filter_map(
+			|entry| {
+				let pid = entry.ok()?.file_name().to_str()?.parse().ok()?;
+				let stat = std::fs::read(format!("/proc/{}/stat", pid)).ok()?;
+
+				// The command name may contain spaces and parentheses, but it is the only
+				// field that may, and it is enclosed in parentheses. The parent pid
+				// follows the state field.
+				let fields = &stat[stat.iter().rposition(|&c| c == b')')? + 1 ..];
+				let ppid = std::str::from_utf8(fields)
+					.ok()?
+					.split_ascii_whitespace()
+					.nth(1)?
+					.parse()
+					.ok()?;
+
+				Some((pid, ppid))
+			}
+		)
+		.collect();
+
+	let mut tree = vec![pid];
+	let mut ix = 0;
+
+	while let Some(&parent) = tree.get(ix) {
+		tree.extend(
+			parents
+				.iter()
+				.filter(|&&(_, ppid)| ppid == parent)
+				.map(|&(pid, _)| pid)
+		);
+
+		ix += 1;
+	}
+
+	tree
+}
+
+
+/// Whether there are signals to be dispatched.
+pub fn is_pending() -> bool {
+	PENDING.load(Ordering::Relaxed) != 0
+}
+
+
+/// Take the signals to be dispatched, in ascending order.
+pub fn take_pending() -> impl Iterator<Item = libc::c_int> {
+	let pending = PENDING.swap(0, Ordering::SeqCst);
+
+	(1 .. 64).filter(move |signal| pending & (1 << signal) != 0)
+}
+
+
+fn set_disposition(signal: libc::c_int, handler: libc::sighandler_t) -> io::Result<()> {
+	// SAFETY: the action is fully initialized, and the handler is async-signal-safe.
+	let result = unsafe {
+		let mut action: libc::sigaction = std::mem::zeroed();
+		action.sa_sigaction = handler;
+		action.sa_flags = libc::SA_RESTART;
+		libc::sigemptyset(&mut action.sa_mask);
+
+		libc::sigaction(signal, &action, std::ptr::null_mut())
+	};
+
+	if result < 0 {
+		Err(io::Error::last_os_error())
+	} else {
+		Ok(())
+	}
+}
+
+
+/// Get the number of a signal, given either as an int or by name, with or without the
+/// SIG prefix.
+pub fn number(value: &Value) -> Option<libc::c_int> {
+	let name = match value {
+		Value::Int(signal) => return libc::c_int
+			::try_from(*signal)
+			.ok()
+			.filter(|&signal| signal > 0 && signal < 32),
+		Value::String(name) => name.as_bytes(),
+		_ => return None,
+	};
+
+	let name = name.strip_prefix(b"SIG").unwrap_or(name);
+
+	let signal = match name {
+		b"HUP" => libc::SIGHUP,
+		b"INT" => libc::SIGINT,
+		b"QUIT" => libc::SIGQUIT,
+		b"ABRT" => libc::SIGABRT,
+		b"KILL" => libc::SIGKILL,
+		b"USR1" => libc::SIGUSR1,
+		b"USR2" => libc::SIGUSR2,
+		b"PIPE" => libc::SIGPIPE,
+		b"ALRM" => libc::SIGALRM,
+		b"TERM" => libc::SIGTERM,
+		b"CHLD" => libc::SIGCHLD,
+		b"CONT" => libc::SIGCONT,
+		b"STOP" => libc::SIGSTOP,
+		b"TSTP" => libc::SIGTSTP,
+		b"TTIN" => libc::SIGTTIN,
+		b"TTOU" => libc::SIGTTOU,
+		b"WINCH" => libc::SIGWINCH,
+		_ => return None,
+	};
+
+	Some(signal)
+}
@@ -16,6 +16,9 @@ pub struct SourcePos {
 	pub line: u32,
 	pub column: u32,
 	pub path: Symbol,
+	/// The length in bytes of the token at the position, limited to its line. Zero if
+	/// unknown.
+	pub len: u32,
 }
 
 
@@ -28,7 +31,7 @@ impl SourcePos {
 
 	/// Create a new SourcePos refering to the beginning of the file.
 	pub fn file(path: Symbol) -> Self {
-		Self { line: 0, column: 0, path }
+		Self { line: 0, column: 0, path, len: 0 }
 	}
 }
 
@@ -60,6 +63,7 @@ impl From<syntax::SourcePos> for SourcePos {
 			line: pos.line,
 			column: pos.column,
 			path: pos.path,
+			len: pos.len,
 		}
 	}
 }
@@ -72,6 +76,18 @@ impl<'a> From<&'a syntax::SourcePos> for SourcePos {
 }
 
 
+impl<'a> From<&'a SourcePos> for syntax::SourcePos {
+	fn from(pos: &'a SourcePos) -> Self {
+		Self {
+			line: pos.line,
+			column: pos.column,
+			path: pos.path,
+			len: pos.len,
+		}
+	}
+}
+
+
 impl<'a> Display<'a> for SourcePos {
 	type Context = &'a symbol::Interner;
 
@@ -2,6 +2,7 @@ use std::{
 	io,
 	path::Path,
 	os::unix::ffi::OsStrExt,
+	time::{Duration, Instant},
 };
 
 use serial_test::serial;
@@ -13,7 +14,7 @@ use crate::{
 	syntax::{self, AnalysisDisplayContext},
 	tests,
 };
-use super::{Runtime, Value, Panic};
+use super::{panic::PanicKind, Runtime, Value, Panic, PanicDisplayContext};
 
 
 fn test_dir<P, F>(path: P, mut check: F) -> io::Result<()>
@@ -32,6 +33,7 @@ where
 				.interner_mut()
 				.get_or_intern(path.as_os_str().as_bytes());
 			let source = syntax::Source::from_reader(path_symbol, file)?;
+			runtime.sources_mut().insert(&source);
 			let syntactic_analysis = syntax::Analysis::analyze(
 				&source,
 				runtime.interner_mut()
@@ -45,6 +47,7 @@ where
 						AnalysisDisplayContext {
 							max_errors: None,
 							interner: runtime.interner(),
+							sources: runtime.sources(),
 						}
 					)
 				);
@@ -63,6 +66,7 @@ where
 						ErrorsDisplayContext {
 							max_errors: None,
 							interner: runtime.interner(),
+							sources: runtime.sources(),
 						}
 					)
 				),
@@ -79,7 +83,16 @@ where
 						path.display(),
 						fmt::Show(value, runtime.interner())
 					),
-					Err(panic) => panic!("{}", fmt::Show(panic, runtime.interner())),
+					Err(panic) => panic!(
+						"{}",
+						fmt::Show(
+							panic,
+							PanicDisplayContext {
+								interner: runtime.interner(),
+								sources: runtime.sources(),
+							}
+						)
+					),
 				}
 			}
 
@@ -118,6 +131,95 @@ fn test_negative() -> io::Result<()> {
 fn test_asserts() -> io::Result<()> {
 	test_dir(
 		"src/runtime/tests/data/negative/asserts",
-		|result| matches!(result, Err(Panic::AssertionFailed { .. }))
+		|result| matches!(
+			result,
+			Err(panic) if matches!(*panic.kind, PanicKind::AssertionFailed { .. })
+		)
 	)
 }
+
+
+/// Evaluate each input in the same interactive session, returning the result of each.
+fn eval_session(inputs: &[&str]) -> Vec<Result<Value, ()>> {
+	let mut interner = symbol::Interner::new();
+	let path = interner.get_or_intern("<session>");
+	let mut session = semantic::Session::new(&mut interner);
+	let mut runtime = Runtime::new(std::iter::empty::<&str>(), interner);
+
+	inputs
+		.iter()
+		.map(
+			|input| {
+				let source = syntax::Source { path, contents: input.as_bytes().into() };
+				let syntactic_analysis = syntax::Analysis::analyze(&source, runtime.interner_mut());
+				assert!(syntactic_analysis.is_ok(), "syntax error in {:?}", input);
+
+				let program = semantic::Analyzer::analyze_session(
+					syntactic_analysis.ast,
+					runtime.interner_mut(),
+					&mut session,
+				);
+
+				match program {
+					Ok(program) => runtime
+						.eval_session(Box::leak(Box::new(program)))
+						.map_err(|_| ()),
+					Err(_) => Err(()),
+				}
+			}
+		)
+		.collect()
+}
+
+
+#[test]
+#[serial]
+fn test_session() {
+	let results = eval_session(
+		&[
+			"let x = 1",
+			"let double = function(n) return n * 2 end",
+			"x + 1",
+			// Semantic error: the session must be left untouched.
+			"let y = 2\nundeclared",
+			"let y = 3",
+			// Panic: the root frame must be kept.
+			"x = 5\nstd.assert(false)",
+			"double(x) + y",
+		]
+	);
+
+	assert!(matches!(results[0], Ok(Value::Nil)));
+	assert!(matches!(results[1], Ok(Value::Nil)));
+	assert!(matches!(results[2], Ok(Value::Int(2))));
+	assert!(results[3].is_err());
+	assert!(matches!(results[4], Ok(Value::Nil)));
+	assert!(results[5].is_err());
+	assert!(matches!(results[6], Ok(Value::Int(13))));
+}
+
+
+#[test]
+#[serial]
+fn test_stream_collected() {
+	let results = eval_session(
+		&[
+			"std.int(|{ sh -c 'echo $$; exec sleep 60' }().value)",
+		]
+	);
+
+	let pid = match results[0] {
+		Ok(Value::Int(pid)) => pid as libc::pid_t,
+		ref result => panic!("expected pid, got {:?}", result),
+	};
+
+	// Once the stream is collected, the producer must be terminated and reaped, even if
+	// it is not writing.
+	gc::force_collect();
+
+	let deadline = Instant::now() + Duration::from_secs(5);
+	while unsafe { libc::kill(pid, 0) } == 0 {
+		assert!(Instant::now() < deadline, "stream producer is still running");
+		std::thread::sleep(Duration::from_millis(10));
+	}
+}
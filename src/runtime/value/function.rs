@@ -35,6 +35,14 @@ impl Function {
 			Function::Rust(fun) => Function::Rust(fun.copy()),
 		}
 	}
+
+
+	/// Release the resources held by an unfinished iterator. Hush functions hold none.
+	pub fn close(&self) {
+		if let Function::Rust(fun) = self {
+			fun.close();
+		}
+	}
 }
 
 
@@ -64,8 +72,12 @@ impl<T: NativeFun> From<T> for Function {
 #[derive(Debug)]
 #[derive(Trace, Finalize)]
 pub struct HushFun {
-	/// How many parameters the function expects.
+	/// How many parameters the function expects, excluding the rest parameter.
 	pub params: u32,
+	/// Default values for the trailing parameters.
+	pub defaults: &'static [program::Expr],
+	/// Whether extra arguments are collected in an array.
+	pub variadic: bool,
 	pub frame_info: &'static program::mem::FrameInfo,
 	pub body: &'static program::Block,
 	/// Captured variables, if any.
@@ -78,6 +90,8 @@ pub struct HushFun {
 impl HushFun {
 	pub fn new (
 		params: u32,
+		defaults: &'static [program::Expr],
+		variadic: bool,
 		frame_info: &'static program::mem::FrameInfo,
 		body: &'static program::Block,
 		context: Box<[(Gc<GcCell<Value>>, mem::SlotIx)]>,
@@ -85,6 +99,8 @@ impl HushFun {
 	) -> Self {
 		Self {
 			params,
+			defaults,
+			variadic,
 			frame_info,
 			body,
 			context: Gc::new(context),
@@ -97,6 +113,8 @@ impl HushFun {
 	pub fn copy(&self) -> Self {
 		Self {
 			params: self.params,
+			defaults: self.defaults,
+			variadic: self.variadic,
 			frame_info: self.frame_info,
 			body: self.body,
 			context: self.context.clone(),
@@ -197,6 +215,9 @@ pub trait NativeFun: Trace + Finalize + 'static {
 	fn name(&self) -> &'static str;
 	/// Invoke the function.
 	fn call(&self, context: CallContext) -> Result<Value, Panic>;
+	/// Release the resources held by an iterator, when a for loop stops iterating it
+	/// before it is finished. Does nothing by default.
+	fn close(&self) { }
 }
 
 
@@ -222,6 +243,12 @@ impl RustFun {
 	pub fn call(&self, context: CallContext) -> Result<Value, Panic> {
 		self.0.call(context)
 	}
+
+
+	/// Release the resources held by an unfinished iterator.
+	pub fn close(&self) {
+		self.0.close()
+	}
 }
 
 
@@ -1,9 +1,10 @@
 use std::fmt::Display as _;
 
-use super::{Errors, Error, ErrorKind};
+use super::{Errors, Error, ErrorKind, Warnings, Warning, WarningKind};
 use crate::{
 	fmt::{self, Display},
 	symbol::{self},
+	syntax::{Snippet, SourceMap},
 	term::color
 };
 
@@ -15,6 +16,8 @@ pub struct ErrorsDisplayContext<'a> {
 	pub max_errors: Option<usize>,
 	/// Symbol interner.
 	pub interner: &'a symbol::Interner,
+	/// Source code for snippets.
+	pub sources: &'a SourceMap,
 }
 
 
@@ -41,6 +44,16 @@ impl<'a> Display<'a> for ErrorKind {
 				"'".fmt(f)
 			}
 
+			Self::UnknownType(symbol) => {
+				"unknown type '".fmt(f)?;
+				symbol.fmt(f, context)?;
+				"'".fmt(f)
+			}
+
+			Self::RestParamNotLast => write!(f, "rest parameter must be the last parameter"),
+
+			Self::RequiredParamAfterDefault => write!(f, "required parameter after parameter with default value"),
+
 			Self::ReturnOutsideFunction => write!(f, "return statement outside function"),
 
 			Self::SelfOutsideFunction => write!(f, "self keyword outside function"),
@@ -48,6 +61,7 @@ impl<'a> Display<'a> for ErrorKind {
 			Self::TryOutsideFunction => write!(f, "try operator outside function"),
 
 			Self::BreakOutsideLoop => write!(f, "break statement outside loop"),
+			Self::ContinueOutsideLoop => write!(f, "continue statement outside loop"),
 
 			Self::InvalidAssignment => write!(f, "invalid assignment"),
 
@@ -93,7 +107,12 @@ impl<'a> Display<'a> for Errors {
 				}
 			}
 
-			writeln!(f, "{}", fmt::Show(error, context.interner))?;
+			writeln!(
+				f,
+				"{}{}",
+				fmt::Show(error, context.interner),
+				fmt::Show(Snippet(error.pos), context.sources)
+			)?;
 		}
 
 		Ok(())
@@ -111,3 +130,65 @@ impl std::fmt::Display for Errors {
 		Ok(())
 	}
 }
+
+
+/// Context for displaying warnings.
+#[derive(Debug, Copy, Clone)]
+pub struct WarningsDisplayContext<'a> {
+	/// Max number of displayed warnings.
+	pub max_warnings: Option<usize>,
+	/// Symbol interner.
+	pub interner: &'a symbol::Interner,
+	/// Source code for snippets.
+	pub sources: &'a SourceMap,
+}
+
+
+impl std::fmt::Display for WarningKind {
+	fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+		match self {
+			Self::UnreachableArm => write!(f, "unreachable match arm"),
+		}
+	}
+}
+
+
+impl<'a> Display<'a> for Warning {
+	type Context = &'a symbol::Interner;
+
+	fn fmt(&self, f: &mut std::fmt::Formatter, context: Self::Context) -> std::fmt::Result {
+		write!(f, "{}: {} - ", color::Fg(color::Yellow, "Warning"), fmt::Show(self.pos, context))?;
+		self.kind.fmt(f)
+	}
+}
+
+
+impl<'a> Display<'a> for Warnings {
+	type Context = WarningsDisplayContext<'a>;
+
+	fn fmt(&self, f: &mut std::fmt::Formatter, context: Self::Context) -> std::fmt::Result {
+		for (ix, warning) in self.0.iter().enumerate() {
+			if let Some(max) = context.max_warnings {
+				if max <= ix {
+					writeln!(
+						f,
+						"{} {}",
+						color::Fg(color::Yellow, max),
+						color::Fg(color::Yellow, "more suppressed semantic warnings"),
+					)?;
+
+					break;
+				}
+			}
+
+			writeln!(
+				f,
+				"{}{}",
+				fmt::Show(warning, context.interner),
+				fmt::Show(Snippet(warning.pos), context.sources)
+			)?;
+		}
+
+		Ok(())
+	}
+}
@@ -1,7 +1,7 @@
 mod fmt;
 
 use super::{Symbol, SourcePos};
-pub use fmt::ErrorsDisplayContext;
+pub use fmt::{ErrorsDisplayContext, WarningsDisplayContext};
 
 
 /// The kind of a semantic error.
@@ -14,6 +14,12 @@ pub enum ErrorKind {
 	DuplicateVariable(Symbol),
 	/// Duplicate keys in dict literal.
 	DuplicateKey(Symbol),
+	/// Unknown type name in a type pattern.
+	UnknownType(Symbol),
+	/// Rest parameter followed by other parameters.
+	RestParamNotLast,
+	/// Parameter without default value following a parameter with default value.
+	RequiredParamAfterDefault,
 	/// Return statement outside function.
 	ReturnOutsideFunction,
 	/// Self keyword outside function.
@@ -22,6 +28,8 @@ pub enum ErrorKind {
 	TryOutsideFunction,
 	/// Break statement outside loop.
 	BreakOutsideLoop,
+	/// Continue statement outside loop.
+	ContinueOutsideLoop,
 	/// Invalid assignment l-value.
 	InvalidAssignment,
 	/// Built-in command used in async context.
@@ -67,6 +75,33 @@ impl Error {
 	}
 
 
+	/// Unknown type name in a type pattern.
+	pub fn unknown_type(symbol: Symbol, pos: SourcePos) -> Self {
+		Self {
+			kind: ErrorKind::UnknownType(symbol),
+			pos
+		}
+	}
+
+
+	/// Rest parameter followed by other parameters.
+	pub fn rest_param_not_last(pos: SourcePos) -> Self {
+		Self {
+			kind: ErrorKind::RestParamNotLast,
+			pos
+		}
+	}
+
+
+	/// Parameter without default value following a parameter with default value.
+	pub fn required_param_after_default(pos: SourcePos) -> Self {
+		Self {
+			kind: ErrorKind::RequiredParamAfterDefault,
+			pos
+		}
+	}
+
+
 	/// Return statement outside function.
 	pub fn return_outside_function(pos: SourcePos) -> Self {
 		Self {
@@ -103,6 +138,15 @@ impl Error {
 	}
 
 
+	/// Continue statement outside loop.
+	pub fn continue_outside_loop(pos: SourcePos) -> Self {
+		Self {
+			kind: ErrorKind::ContinueOutsideLoop,
+			pos
+		}
+	}
+
+
 	/// Invalid assignment l-value.
 	pub fn invalid_assignment(pos: SourcePos) -> Self {
 		Self {
@@ -149,3 +193,35 @@ impl Extend<Error> for Errors {
 
 
 impl std::error::Error for Errors { }
+
+
+/// The kind of a semantic warning.
+#[derive(Debug)]
+pub enum WarningKind {
+	/// Match arm which can never be executed, as previous arms match all its values.
+	UnreachableArm,
+}
+
+
+/// A semantic warning. Warnings don't prevent the program from being executed.
+#[derive(Debug)]
+pub struct Warning {
+	pub kind: WarningKind,
+	pub pos: SourcePos,
+}
+
+
+impl Warning {
+	/// Match arm which can never be executed, as previous arms match all its values.
+	pub fn unreachable_arm(pos: SourcePos) -> Self {
+		Self {
+			kind: WarningKind::UnreachableArm,
+			pos
+		}
+	}
+}
+
+
+/// A collection of semantic warnings.
+#[derive(Debug, Default)]
+pub struct Warnings(pub Vec<Warning>);
@@ -27,12 +27,49 @@ use program::{
 	Expr,
 	Literal,
 	Lvalue,
+	MatchArm,
+	Pattern,
 	Program,
 	Redirection,
 	RedirectionTarget,
 	Statement,
+	Type,
 };
-pub use error::{Error, ErrorKind, Errors, ErrorsDisplayContext};
+pub use error::{
+	Error,
+	ErrorKind,
+	Errors,
+	ErrorsDisplayContext,
+	Warning,
+	Warnings,
+	WarningsDisplayContext,
+};
+
+
+/// Static analysis state that persists across multiple programs, as in interactive
+/// sessions.
+#[derive(Debug)]
+pub struct Session {
+	/// Scope stack holding the root frame.
+	scope: scope::Stack,
+}
+
+
+impl Session {
+	/// Create a new session, entering the root frame.
+	pub fn new(interner: &mut symbol::Interner) -> Self {
+		let mut scope = scope::Stack::default();
+		Analyzer::enter_root_frame(&mut scope, interner);
+		Self { scope }
+	}
+}
+
+
+impl Drop for Session {
+	fn drop(&mut self) {
+		self.scope.exit_frame();
+	}
+}
 
 
 /// Static semantic analyzer.
@@ -40,6 +77,8 @@ pub use error::{Error, ErrorKind, Errors, ErrorsDisplayContext};
 pub struct Analyzer<'a> {
 	/// Collected errors.
 	errors: &'a mut Errors,
+	/// Collected warnings.
+	warnings: &'a mut Warnings,
 	/// Scope stack to check declared variables.
 	scope: &'a mut scope::Stack,
 	/// Hashset to check duplicate symbols in dict keys.
@@ -64,9 +103,16 @@ impl<'a> Analyzer<'a> {
 		let mut scope = scope::Stack::default();
 		let mut dict_keys = HashSet::default();
 		let mut errors = Errors::default();
+		let mut warnings = Warnings::default();
 
 		let (result, root_frame) = {
-			let mut analyzer = Analyzer::new(interner, &mut scope, &mut dict_keys, &mut errors);
+			let mut analyzer = Analyzer::new(
+				interner,
+				&mut scope,
+				&mut dict_keys,
+				&mut errors,
+				&mut warnings
+			);
 			let result = analyzer.analyze_block(ast.statements);
 			let root_frame = analyzer.exit_frame();
 			(result, root_frame)
@@ -79,6 +125,7 @@ impl<'a> Analyzer<'a> {
 					source: ast.source,
 					statements,
 					root_slots: root_frame.slots,
+					warnings,
 				}
 			),
 
@@ -87,6 +134,53 @@ impl<'a> Analyzer<'a> {
 	}
 
 
+	/// Perform static semantic analysis in the given AST, in the context of an interactive
+	/// session. Variables declared in the root scope are kept in the session, and are
+	/// visible to subsequent analyses. If any error is detected, the session is left
+	/// untouched.
+	pub fn analyze_session(
+		ast: ast::Ast,
+		interner: &mut symbol::Interner,
+		session: &mut Session,
+	) -> Result<Program, Errors> {
+		let mut dict_keys = HashSet::default();
+		let mut errors = Errors::default();
+		let mut warnings = Warnings::default();
+
+		let checkpoint = session.scope.checkpoint();
+
+		let result = {
+			let mut analyzer = Analyzer::with_scope(
+				interner,
+				&mut session.scope,
+				&mut dict_keys,
+				&mut errors,
+				&mut warnings
+			);
+			let result = analyzer.analyze_block(ast.statements);
+			// The root scope must outlive the analyzer.
+			analyzer.dropped = true;
+			result
+		};
+
+		match result {
+			Some(statements) if errors.0.is_empty() => Ok(
+				Program {
+					source: ast.source,
+					statements,
+					root_slots: session.scope.slots(),
+					warnings,
+				}
+			),
+
+			_ => {
+				session.scope.restore(checkpoint);
+				Err(errors)
+			}
+		}
+	}
+
+
 	/// Analyze a block.
 	/// None is returned if any error is detected.
 	fn analyze_block(&mut self, block: ast::Block) -> Option<Block> {
@@ -127,6 +221,16 @@ impl<'a> Analyzer<'a> {
 				Some(Statement::Assign { left, right })
 			}
 
+			// Let destructuring.
+			ast::Statement::Destructure { pattern, init, pos } => {
+				let pattern = self.analyze_pattern(pattern);
+				let init = self.analyze_expr(init);
+
+				let (pattern, right) = pattern.zip(init)?;
+
+				Some(Statement::Destructure { pattern, right, pos })
+			}
+
 			// Assign.
 			ast::Statement::Assign { left, right, pos } => {
 				let left = self
@@ -145,6 +249,24 @@ impl<'a> Analyzer<'a> {
 				Some(Statement::Assign { left, right })
 			}
 
+			// Compound assign.
+			ast::Statement::CompoundAssign { left, op, right, pos } => {
+				let left = self
+					.analyze_lvalue(left)
+					.map_err(
+						|lvalue| if !lvalue {
+							self.report(Error::invalid_assignment(pos));
+						}
+					)
+					.ok();
+
+				let right = self.analyze_expr(right);
+
+				let (left, right) = left.zip(right)?;
+
+				Some(Statement::CompoundAssign { left, op: op.into(), right, pos })
+			}
+
 			// Return.
 			ast::Statement::Return { expr, pos } => {
 				let ret =
@@ -172,6 +294,16 @@ impl<'a> Analyzer<'a> {
 				}
 			}
 
+			// Continue.
+			ast::Statement::Continue { pos } => {
+				if self.in_loop {
+					Some(Statement::Continue)
+				} else {
+					self.report(Error::continue_outside_loop(pos));
+					None
+				}
+			}
+
 			// While.
 			ast::Statement::While { condition, block, .. } => {
 				let condition = self.analyze_expr(condition);
@@ -185,31 +317,20 @@ impl<'a> Analyzer<'a> {
 			}
 
 			// For.
-			ast::Statement::For { identifier, expr, block, pos } => {
+			ast::Statement::For { pattern, expr, block, pos } => {
 				let expr = self.analyze_expr(expr);
-				let id_block = {
+				let pattern_block = {
 					let mut analyzer = self.enter_loop();
 
-					let slot_ix =
-						if identifier.is_ill_formed() {
-							None
-						} else {
-							analyzer.scope
-								.declare(identifier, pos)
-								.map_err(
-									|error| analyzer.report(error)
-								)
-								.ok()
-						};
-
+					let pattern = analyzer.analyze_pattern(pattern);
 					let block = analyzer.analyze_block(block);
 
-					slot_ix.zip(block)
+					pattern.zip(block)
 				};
 
-				let (expr, (slot_ix, block)) = expr.zip(id_block)?;
+				let (expr, (pattern, block)) = expr.zip(pattern_block)?;
 
-				Some(Statement::For { slot_ix, expr, block })
+				Some(Statement::For { pattern, expr, block, pos })
 			}
 
 			// Expr.
@@ -322,6 +443,33 @@ impl<'a> Analyzer<'a> {
 				)
 			}
 
+			// Match.
+			ast::Expr::Match { value, arms, pos } => {
+				let value = self.analyze_expr(*value);
+
+				let arms_pos: Box<[SourcePos]> = arms
+					.iter()
+					.map(|arm| arm.pos)
+					.collect();
+
+				let arms = self.analyze_items(
+					Self::analyze_match_arm,
+					arms.into_vec(), // Use vec's owned iterator.
+				);
+
+				let (value, arms) = value.zip(arms)?;
+
+				self.check_unreachable_arms(&arms, &arms_pos);
+
+				Some(
+					Expr::Match {
+						value: Box::new(value),
+						arms,
+						pos,
+					}
+				)
+			}
+
 			// Access.
 			ast::Expr::Access { object, field, pos } => {
 				let object = self.analyze_expr(*object);
@@ -365,12 +513,174 @@ impl<'a> Analyzer<'a> {
 				Some(Expr::CommandBlock { block, pos })
 			},
 
+			// Interpolation.
+			ast::Expr::Interpolation { parts, pos } => {
+				let parts = self.analyze_items(
+					Self::analyze_expr,
+					parts.into_vec(), // Use vec's owned iterator.
+				)?;
+
+				Some(Expr::Interpolation { parts, pos })
+			}
+
 			// Ill-formed.
 			ast::Expr::IllFormed => None,
 		}
 	}
 
 
+	/// Analyze a match arm. The pattern bindings are declared in the arm's block scope.
+	/// None is returned if any error is detected.
+	fn analyze_match_arm(&mut self, arm: ast::MatchArm) -> Option<MatchArm> {
+		let mut analyzer = self.enter_block();
+
+		let pattern = analyzer.analyze_pattern(arm.pattern);
+		let guard = arm.guard.map(|guard| analyzer.analyze_expr(guard));
+		let block = analyzer.analyze_block(arm.block);
+
+		let (pattern, block) = pattern.zip(block)?;
+
+		let guard = match guard {
+			Some(guard) => Some(guard?),
+			None => None,
+		};
+
+		Some(MatchArm { pattern, guard, block })
+	}
+
+
+	/// Analyze a pattern, declaring its bindings in the current scope.
+	/// None is returned if any error is detected.
+	fn analyze_pattern(&mut self, pattern: ast::Pattern) -> Option<Pattern> {
+		match pattern {
+			// Literal.
+			ast::Pattern::Literal { literal, .. } => {
+				let literal = self.analyze_literal(literal)?;
+				Some(Pattern::Literal(literal))
+			}
+
+			// Binding.
+			ast::Pattern::Binding { identifier, pos } => {
+				if identifier.is_ill_formed() {
+					None
+				} else if self.interner.resolve(identifier) == Some(b"_") {
+					Some(Pattern::Wildcard)
+				} else {
+					let slot_ix = self.scope
+						.declare(identifier, pos)
+						.map_err(
+							|error| self.report(error)
+						)
+						.ok()?;
+
+					Some(Pattern::Binding { slot_ix })
+				}
+			}
+
+			// Type.
+			ast::Pattern::Type { pattern, type_name, pos } => {
+				let pattern = self.analyze_pattern(*pattern);
+				let type_name = self.analyze_type_name(type_name, pos);
+
+				let (pattern, type_name) = pattern.zip(type_name)?;
+
+				Some(
+					Pattern::Type {
+						pattern: Box::new(pattern),
+						type_name,
+					}
+				)
+			}
+
+			// Array.
+			ast::Pattern::Array { items, .. } => {
+				let items = self.analyze_items(
+					Self::analyze_pattern,
+					items.into_vec(), // Use vec's owned iterator.
+				)?;
+
+				Some(Pattern::Array(items))
+			}
+
+			// Dict.
+			ast::Pattern::Dict { items, .. } => {
+				// Patterns may be nested, so we can't use the shared dict keys set.
+				let mut keys = HashSet::new();
+
+				let items = self.analyze_items(
+					|analyzer, ((symbol, pos), pattern)| {
+						let symbol =
+							if symbol.is_ill_formed() {
+								None
+							} else if keys.insert(symbol) {
+								Some(symbol)
+							} else { // Duplicate symbol.
+								analyzer.report(Error::duplicate_key(symbol, pos));
+								None
+							};
+
+						let pattern = analyzer.analyze_pattern(pattern);
+
+						let (symbol, pattern) = symbol.zip(pattern)?;
+						let key = analyzer.interner
+							.resolve(symbol)
+							.expect("unresolved symbol")
+							.into();
+
+						Some((key, pattern))
+					},
+					items.into_vec(), // Use vec's owned iterator.
+				)?;
+
+				Some(Pattern::Dict(items))
+			}
+
+			// Ill-formed.
+			ast::Pattern::IllFormed => None,
+		}
+	}
+
+
+	/// Analyze a type name, producing the corresponding type.
+	/// None is returned if the type is unknown.
+	fn analyze_type_name(&mut self, type_name: ast::TypeName, pos: SourcePos) -> Option<Type> {
+		match type_name {
+			ast::TypeName::Nil => Some(Type::Nil),
+
+			ast::TypeName::Function => Some(Type::Function),
+
+			ast::TypeName::Named(symbol) if symbol.is_ill_formed() => None,
+
+			ast::TypeName::Named(symbol) => {
+				let type_name = self.interner
+					.resolve(symbol)
+					.and_then(Type::parse);
+
+				if type_name.is_none() {
+					self.report(Error::unknown_type(symbol, pos));
+				}
+
+				type_name
+			}
+		}
+	}
+
+
+	/// Warn about match arms which are subsumed by a previous unguarded arm.
+	fn check_unreachable_arms(&mut self, arms: &[MatchArm], arms_pos: &[SourcePos]) {
+		for (ix, (arm, &pos)) in arms.iter().zip(arms_pos).enumerate() {
+			let unreachable = arms[.. ix]
+				.iter()
+				.filter(|previous| previous.guard.is_none())
+				.any(|previous| previous.pattern.subsumes(&arm.pattern));
+
+			if unreachable {
+				self.warn(Warning::unreachable_arm(pos));
+			}
+		}
+	}
+
+
 	/// Analyze an l-value expression.
 	/// Err is returned if any error is detected. The boolean indicates if the expression is
 	/// a valid l-value.
@@ -484,13 +794,15 @@ impl<'a> Analyzer<'a> {
 
 			// Function.
 			ast::Literal::Function { params, body } => {
+				let params_len = params.len();
 				let mut analyzer = self.enter_frame();
 
+				// Parameters must be declared first, as they are placed in the first slots.
 				let params_result = params
 					.iter()
 					.fold(
 						Some(()),
-						|acc, &(symbol ,pos)| {
+						|acc, &ast::Param { identifier: symbol, pos, .. }| {
 							let result = if symbol.is_ill_formed() {
 								None
 							} else {
@@ -507,15 +819,47 @@ impl<'a> Analyzer<'a> {
 						}
 					);
 
+				let mut variadic = false;
+				let mut has_default = false;
+				let mut defaults = Vec::new();
+
+				for (ix, param) in params.into_vec().into_iter().enumerate() {
+					match param.kind {
+						ast::ParamKind::Required if has_default => {
+							analyzer.report(Error::required_param_after_default(param.pos));
+						}
+
+						ast::ParamKind::Required => (),
+
+						ast::ParamKind::Default(expr) => {
+							has_default = true;
+							defaults.push(expr);
+						}
+
+						ast::ParamKind::Rest if ix + 1 != params_len => {
+							analyzer.report(Error::rest_param_not_last(param.pos));
+						}
+
+						ast::ParamKind::Rest => variadic = true,
+					}
+				}
+
+				let defaults = analyzer.analyze_items(
+					|analyzer, expr| analyzer.analyze_expr(expr),
+					defaults,
+				);
+
 				let body = analyzer.analyze_block(body);
 
 				let frame_info = analyzer.exit_frame();
 
-				let (_, body) = params_result.zip(body)?;
+				let (_, (defaults, body)) = params_result.zip(defaults.zip(body))?;
 
 				Some(
 					Literal::Function {
-						params: params.len() as u32,
+						params: params_len as u32 - variadic as u32,
+						defaults,
+						variadic,
 						frame_info,
 						body
 					}
@@ -739,6 +1083,8 @@ impl<'a> Analyzer<'a> {
 				let target = match target {
 					ast::RedirectionTarget::Fd(fd) => Some(RedirectionTarget::Fd(fd)),
 
+					ast::RedirectionTarget::Close => Some(RedirectionTarget::Close),
+
 					ast::RedirectionTarget::Overwrite(arg) => self
 						.analyze_argument(arg)
 						.map(RedirectionTarget::Overwrite),
@@ -751,10 +1097,10 @@ impl<'a> Analyzer<'a> {
 				Some(Redirection::Output { source, target })
 			},
 
-			ast::Redirection::Input { literal, source } => {
+			ast::Redirection::Input { target, literal, source } => {
 				let source = self.analyze_argument(source)?;
 
-				Some(Redirection::Input { literal, source })
+				Some(Redirection::Input { target, literal, source })
 			}
 		}
 	}
@@ -794,17 +1140,25 @@ impl<'a> Analyzer<'a> {
 		interner: &'a mut symbol::Interner,
 		scope: &'a mut scope::Stack,
 		dict_keys: &'a mut HashSet<Symbol>,
-		errors: &'a mut Errors
+		errors: &'a mut Errors,
+		warnings: &'a mut Warnings
 	) -> Self {
-		let std_symbol = interner.get_or_intern("std");
+		Self::enter_root_frame(scope, interner);
+		Self::with_scope(interner, scope, dict_keys, errors, warnings)
+	}
 
-		scope.enter_frame();
-		scope
-			.declare(std_symbol, SourcePos::default())
-			.expect("failed to insert std symbol");
 
+	/// Create a new analyzer over an already entered root frame.
+	fn with_scope(
+		interner: &'a mut symbol::Interner,
+		scope: &'a mut scope::Stack,
+		dict_keys: &'a mut HashSet<Symbol>,
+		errors: &'a mut Errors,
+		warnings: &'a mut Warnings
+	) -> Self {
 		Self {
 			errors,
+			warnings,
 			scope,
 			dict_keys,
 			interner,
@@ -815,12 +1169,24 @@ impl<'a> Analyzer<'a> {
 	}
 
 
+	/// Enter the root frame, where the stdlib is declared.
+	fn enter_root_frame(scope: &mut scope::Stack, interner: &mut symbol::Interner) {
+		let std_symbol = interner.get_or_intern("std");
+
+		scope.enter_frame();
+		scope
+			.declare(std_symbol, SourcePos::default())
+			.expect("failed to insert std symbol");
+	}
+
+
 	/// Enter a new block scope.
 	fn enter_block(&mut self) -> Analyzer {
 		self.scope.enter_block();
 
 		Analyzer {
 			errors: self.errors,
+			warnings: self.warnings,
 			scope: self.scope,
 			dict_keys: self.dict_keys,
 			interner: self.interner,
@@ -837,6 +1203,7 @@ impl<'a> Analyzer<'a> {
 
 		Analyzer {
 			errors: self.errors,
+			warnings: self.warnings,
 			scope: self.scope,
 			dict_keys: self.dict_keys,
 			interner: self.interner,
@@ -853,6 +1220,7 @@ impl<'a> Analyzer<'a> {
 
 		Analyzer {
 			errors: self.errors,
+			warnings: self.warnings,
 			scope: self.scope,
 			dict_keys: self.dict_keys,
 			interner: self.interner,
@@ -874,6 +1242,12 @@ impl<'a> Analyzer<'a> {
 	fn report(&mut self, error: Error) {
 		self.errors.0.push(error);
 	}
+
+
+	/// Report a warning.
+	fn warn(&mut self, warning: Warning) {
+		self.warnings.0.push(warning);
+	}
 }
 
 
@@ -49,6 +49,8 @@ pub enum RedirectionTarget {
 	Overwrite(Argument),
 	/// Append to a file.
 	Append(Argument),
+	/// Close the file descriptor.
+	Close,
 }
 
 
@@ -56,12 +58,16 @@ pub enum RedirectionTarget {
 #[derive(Debug)]
 pub enum Redirection {
 	/// Redirect output to a file or file descriptor.
+	/// Input duplication (`N<&M`) is equivalent to output duplication, and is also
+	/// represented by this variant.
 	Output {
 		source: FileDescriptor,
 		target: RedirectionTarget,
 	},
 	/// Redirect input from a file or literal.
 	Input {
+		/// The file descriptor to be redirected.
+		target: FileDescriptor,
 		/// Whether the source is the input or the file path.
 		literal: bool,
 		source: Argument,
@@ -74,6 +80,7 @@ pub enum Redirection {
 pub enum Builtin {
 	Alias,
 	Cd,
+	Unalias,
 }
 
 
@@ -91,6 +98,7 @@ impl<'a> TryFrom<&'a [u8]> for Builtin {
 		match value {
 			b"alias" => Ok(Self::Alias),
 			b"cd" => Ok(Self::Cd),
+			b"unalias" => Ok(Self::Unalias),
 			_ => Err(InvalidBuiltin)
 		}
 	}
@@ -153,6 +161,7 @@ pub enum CommandBlockKind {
 	Synchronous,  // {}
 	Asynchronous, // &{}
 	Capture,      // ${}
+	Stream,       // |{}
 }
 
 
@@ -162,6 +171,7 @@ impl From<ast::CommandBlockKind> for CommandBlockKind {
 			ast::CommandBlockKind::Synchronous => CommandBlockKind::Synchronous,
 			ast::CommandBlockKind::Asynchronous => CommandBlockKind::Asynchronous,
 			ast::CommandBlockKind::Capture => CommandBlockKind::Capture,
+			ast::CommandBlockKind::Stream => CommandBlockKind::Stream,
 		}
 	}
 }
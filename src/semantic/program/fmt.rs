@@ -17,6 +17,8 @@ use super::{
 	Expr,
 	Literal,
 	Lvalue,
+	MatchArm,
+	Pattern,
 	Redirection,
 	RedirectionTarget,
 	Statement,
@@ -149,7 +151,7 @@ impl<'a> Display<'a> for Literal {
 				"]".fmt(f)
 			},
 
-			Self::Function { params, frame_info, body } => {
+			Self::Function { params, defaults, variadic, frame_info, body } => {
 				let step = if context.indentation.is_some() { "\n" } else { " " };
 
 				Keyword::Function.fmt(f)?;
@@ -157,6 +159,15 @@ impl<'a> Display<'a> for Literal {
 
 				params.fmt(f)?;
 
+				if !defaults.is_empty() {
+					"; ".fmt(f)?;
+					fmt::sep_by(defaults.iter(), f, |expr, f| expr.fmt(f, context.inlined()), ", ")?;
+				}
+
+				if *variadic {
+					"; ...".fmt(f)?;
+				}
+
 				")".fmt(f)?;
 
 				if context.indentation.is_some() {
@@ -198,7 +209,14 @@ impl std::fmt::Display for BinaryOp {
 			Self::Minus => Operator::Minus.fmt(f),
 			Self::Times => Operator::Times.fmt(f),
 			Self::Div => Operator::Div.fmt(f),
+			Self::IntDiv => Operator::IntDiv.fmt(f),
 			Self::Mod => Operator::Mod.fmt(f),
+			Self::Pow => Operator::Pow.fmt(f),
+			Self::BitAnd => Operator::BitAnd.fmt(f),
+			Self::BitOr => Operator::BitOr.fmt(f),
+			Self::BitXor => Operator::BitXor.fmt(f),
+			Self::ShiftLeft => Operator::ShiftLeft.fmt(f),
+			Self::ShiftRight => Operator::ShiftRight.fmt(f),
 			Self::Equals => Operator::Equals.fmt(f),
 			Self::NotEquals => Operator::NotEquals.fmt(f),
 			Self::Greater => Operator::Greater.fmt(f),
@@ -213,6 +231,81 @@ impl std::fmt::Display for BinaryOp {
 }
 
 
+impl<'a> Display<'a> for Pattern {
+	type Context = Context<'a>;
+
+	fn fmt(&self, f: &mut std::fmt::Formatter, context: Self::Context) -> std::fmt::Result {
+		match self {
+			Self::Wildcard => "_".fmt(f),
+
+			Self::Literal(literal) => literal.fmt(f, context),
+
+			Self::Binding { slot_ix } => slot_ix.fmt(f),
+
+			Self::Type { pattern, type_name } => {
+				pattern.fmt(f, context)?;
+				": ".fmt(f)?;
+				type_name.fmt(f)
+			}
+
+			Self::Array(items) => {
+				"[".fmt(f)?;
+				fmt::sep_by(items.iter(), f, |item, f| item.fmt(f, context), ", ")?;
+				"]".fmt(f)
+			}
+
+			Self::Dict(items) => {
+				"@[".fmt(f)?;
+
+				fmt::sep_by(
+					items.iter(),
+					f,
+					|(key, value), f| {
+						String::from_utf8_lossy(key).fmt(f)?;
+						": ".fmt(f)?;
+						value.fmt(f, context)
+					},
+					", "
+				)?;
+
+				"]".fmt(f)
+			}
+		}
+	}
+}
+
+
+impl<'a> Display<'a> for MatchArm {
+	type Context = Context<'a>;
+
+	fn fmt(&self, f: &mut std::fmt::Formatter, context: Self::Context) -> std::fmt::Result {
+		Keyword::Case.fmt(f)?;
+		" ".fmt(f)?;
+		self.pattern.fmt(f, context.inlined())?;
+
+		if let Some(guard) = &self.guard {
+			" ".fmt(f)?;
+			Keyword::If.fmt(f)?;
+			" ".fmt(f)?;
+			guard.fmt(f, context.inlined())?;
+		}
+
+		" ".fmt(f)?;
+		Keyword::Then.fmt(f)?;
+
+		if !self.block.0.is_empty() {
+			if context.indentation.is_some() {
+				"\n".fmt(f)?;
+			}
+
+			self.block.fmt(f, context.indent())?;
+		}
+
+		Ok(())
+	}
+}
+
+
 impl<'a> Display<'a> for Expr {
 	type Context = Context<'a>;
 
@@ -286,6 +379,24 @@ impl<'a> Display<'a> for Expr {
 				Keyword::End.fmt(f)
 			}
 
+			Self::Match { value, arms, .. } => {
+				Keyword::Match.fmt(f)?;
+				" ".fmt(f)?;
+				value.fmt(f, context.inlined())?;
+				" ".fmt(f)?;
+				Keyword::With.fmt(f)?;
+
+				let nested = context.indent();
+
+				for arm in arms.iter() {
+					step(f, nested)?;
+					arm.fmt(f, nested)?;
+				}
+
+				step(f, context)?;
+				Keyword::End.fmt(f)
+			}
+
 			Self::Access { object, field, .. }
 			if matches!(field.as_ref(), Self::Literal { literal: Literal::Identifier(..), .. }) => {
 				object.fmt(f, context.inlined())?;
@@ -315,6 +426,26 @@ impl<'a> Display<'a> for Expr {
 			}
 
 			Self::CommandBlock { block, .. } => block.fmt(f, context),
+
+			Self::Interpolation { parts, .. } => {
+				"\"".fmt(f)?;
+
+				for part in parts.iter() {
+					match part {
+						Self::Literal { literal: Literal::String(s), .. } => {
+							color::Bold(String::from_utf8_lossy(s).escape_debug()).fmt(f)?
+						}
+
+						expr => {
+							"${".fmt(f)?;
+							expr.fmt(f, context.inlined())?;
+							"}".fmt(f)?;
+						}
+					}
+				}
+
+				"\"".fmt(f)
+			}
 		}
 	}
 }
@@ -349,6 +480,18 @@ impl<'a> Display<'a> for Statement {
 				right.fmt(f, context)
 			}
 
+			Self::Destructure { pattern, right, .. } => {
+				pattern.fmt(f, context.inlined())?;
+				" = ".fmt(f)?;
+				right.fmt(f, context)
+			}
+
+			Self::CompoundAssign { left, op, right, .. } => {
+				left.fmt(f, context.inlined())?;
+				write!(f, " {}= ", op)?;
+				right.fmt(f, context)
+			}
+
 			Self::Return { expr } => {
 				Keyword::Return.fmt(f)?;
 				" ".fmt(f)?;
@@ -357,6 +500,8 @@ impl<'a> Display<'a> for Statement {
 
 			Self::Break => Keyword::Break.fmt(f),
 
+			Self::Continue => Keyword::Continue.fmt(f),
+
 			Self::While { condition, block } => {
 				let step = if context.indentation.is_some() { "\n" } else { " " };
 
@@ -379,12 +524,12 @@ impl<'a> Display<'a> for Statement {
 				Keyword::End.fmt(f)
 			}
 
-			Self::For { slot_ix, expr, block } => {
+			Self::For { pattern, expr, block, .. } => {
 				let step = if context.indentation.is_some() { "\n" } else { " " };
 
 				Keyword::For.fmt(f)?;
 				" ".fmt(f)?;
-				slot_ix.fmt(f)?;
+				pattern.fmt(f, context.inlined())?;
 				" ".fmt(f)?;
 				Keyword::In.fmt(f)?;
 				" ".fmt(f)?;
@@ -487,7 +632,9 @@ impl std::fmt::Display for Argument {
 impl std::fmt::Display for RedirectionTarget {
 	fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
 		match self {
-			Self::Fd(fd) => write!(f, ">{}", fd),
+			Self::Fd(fd) => write!(f, ">&{}", fd),
+
+			Self::Close => ">&-".fmt(f),
 
 			Self::Overwrite(arg) => {
 				">".fmt(f)?;
@@ -511,12 +658,14 @@ impl std::fmt::Display for Redirection {
 				target.fmt(f)
 			}
 
-			Self::Input { literal: false, source } => {
+			Self::Input { target, literal: false, source } => {
+				target.fmt(f)?;
 				"<".fmt(f)?;
 				source.fmt(f)
 			}
 
-			Self::Input { literal: true, source } => {
+			Self::Input { target, literal: true, source } => {
+				target.fmt(f)?;
 				"<<".fmt(f)?;
 				source.fmt(f)
 			}
@@ -530,6 +679,7 @@ impl std::fmt::Display for command::Builtin {
 		let command = match self {
 			command::Builtin::Alias => "alias",
 			command::Builtin::Cd => "cd",
+			command::Builtin::Unalias => "unalias",
 		};
 
 		color::Fg(color::Green, command).fmt(f)
@@ -615,6 +765,7 @@ impl std::fmt::Display for CommandBlockKind {
 			Self::Synchronous => "{",
 			Self::Asynchronous => "&{",
 			Self::Capture => "${",
+			Self::Stream => "|{",
 		}.fmt(f)
 	}
 }
@@ -2,8 +2,9 @@ pub mod command;
 pub mod fmt;
 pub mod mem;
 
-use super::{ast, lexer};
+use super::{ast, lexer, Warnings};
 pub use crate::{
+	runtime::value::Type,
 	syntax::SourcePos,
 	symbol::Symbol,
 };
@@ -45,8 +46,14 @@ pub enum Literal {
 	Array(Box<[Expr]>),
 	Dict(Box<[(Symbol, Expr)]>),
 	Function {
-		/// The number of parameters.
+		/// The number of parameters, excluding the rest parameter.
 		params: u32,
+		/// The default values for the trailing parameters, evaluated in the function's scope
+		/// when the arguments are not supplied.
+		defaults: Box<[Expr]>,
+		/// Whether the extra arguments are collected in an array, in the slot following
+		/// the parameters.
+		variadic: bool,
 		frame_info: mem::FrameInfo,
 		body: Block,
 	},
@@ -57,6 +64,84 @@ pub enum Literal {
 }
 
 
+/// Patterns for match expressions.
+#[derive(Debug)]
+pub enum Pattern {
+	/// Matches any value.
+	Wildcard,
+	/// Matches values equal to the literal. Only basic literals are allowed.
+	Literal(Literal),
+	/// Matches any value, storing it in the given slot.
+	Binding {
+		slot_ix: mem::SlotIx,
+	},
+	/// Matches values of the given type which also match the inner pattern.
+	Type {
+		pattern: Box<Pattern>,
+		type_name: Type,
+	},
+	/// Matches arrays of the same length whose items match the inner patterns.
+	Array(Box<[Pattern]>),
+	/// Matches dicts containing the given string keys whose values match the inner
+	/// patterns.
+	Dict(Box<[(Box<[u8]>, Pattern)]>),
+}
+
+
+impl Pattern {
+	/// Check if every value matched by the other pattern is also matched by this pattern.
+	/// This is a conservative check, which may produce false negatives.
+	pub(in crate::semantic) fn subsumes(&self, other: &Self) -> bool {
+		match (self, other) {
+			(Self::Wildcard, _) | (Self::Binding { .. }, _) => true,
+
+			(Self::Literal(literal), Self::Literal(other)) => match (literal, other) {
+				(Literal::Nil, Literal::Nil) => true,
+				(Literal::Bool(b1), Literal::Bool(b2)) => b1 == b2,
+				(Literal::Int(i1), Literal::Int(i2)) => i1 == i2,
+				(Literal::Float(f1), Literal::Float(f2)) => f1.to_bits() == f2.to_bits(),
+				(Literal::Byte(b1), Literal::Byte(b2)) => b1 == b2,
+				(Literal::String(s1), Literal::String(s2)) => s1 == s2,
+				_ => false,
+			},
+
+			(
+				Self::Type { pattern, type_name },
+				Self::Type { pattern: other, type_name: other_type }
+			) => type_name == other_type && pattern.subsumes(other),
+
+			(Self::Array(items), Self::Array(other)) => {
+				items.len() == other.len()
+					&& items
+						.iter()
+						.zip(other.iter())
+						.all(|(item, other)| item.subsumes(other))
+			}
+
+			(Self::Dict(items), Self::Dict(other)) => items
+				.iter()
+				.all(
+					|(key, pattern)| other
+						.iter()
+						.any(|(other_key, other)| key == other_key && pattern.subsumes(other))
+				),
+
+			_ => false,
+		}
+	}
+}
+
+
+/// An arm of a match expression.
+#[derive(Debug)]
+pub struct MatchArm {
+	pub pattern: Pattern,
+	/// An optional condition, which is checked after the pattern matches.
+	pub guard: Option<Expr>,
+	pub block: Block,
+}
+
+
 /// Unary operators.
 #[derive(Debug)]
 pub enum UnaryOp {
@@ -89,11 +174,19 @@ impl From<ast::UnaryOp> for UnaryOp {
 /// statements/expressions instead.
 #[derive(Debug)]
 pub enum BinaryOp {
-	Plus,  // +
-	Minus, // -
-	Times, // *
-	Div,   // /
-	Mod,   // %
+	Plus,   // +
+	Minus,  // -
+	Times,  // *
+	Div,    // /
+	IntDiv, // //
+	Mod,    // %
+	Pow,    // **
+
+	BitAnd,     // &
+	BitOr,      // |
+	BitXor,     // ^
+	ShiftLeft,  // <<
+	ShiftRight, // >>
 
 	Equals,        // ==
 	NotEquals,     // !=
@@ -116,7 +209,14 @@ impl From<ast::BinaryOp> for BinaryOp {
 			ast::BinaryOp::Minus => BinaryOp::Minus,
 			ast::BinaryOp::Times => BinaryOp::Times,
 			ast::BinaryOp::Div => BinaryOp::Div,
+			ast::BinaryOp::IntDiv => BinaryOp::IntDiv,
 			ast::BinaryOp::Mod => BinaryOp::Mod,
+			ast::BinaryOp::Pow => BinaryOp::Pow,
+			ast::BinaryOp::BitAnd => BinaryOp::BitAnd,
+			ast::BinaryOp::BitOr => BinaryOp::BitOr,
+			ast::BinaryOp::BitXor => BinaryOp::BitXor,
+			ast::BinaryOp::ShiftLeft => BinaryOp::ShiftLeft,
+			ast::BinaryOp::ShiftRight => BinaryOp::ShiftRight,
 			ast::BinaryOp::Equals => BinaryOp::Equals,
 			ast::BinaryOp::NotEquals => BinaryOp::NotEquals,
 			ast::BinaryOp::Greater => BinaryOp::Greater,
@@ -162,6 +262,13 @@ pub enum Expr {
 		otherwise: Block,
 		pos: SourcePos,
 	},
+	/// Match expression. The first arm whose pattern and guard match is executed. If no
+	/// arm matches, the expression evaluates to nil.
+	Match {
+		value: Box<Expr>,
+		arms: Box<[MatchArm]>,
+		pos: SourcePos,
+	},
 	/// Field access ([]) operator.
 	Access {
 		object: Box<Expr>,
@@ -178,6 +285,12 @@ pub enum Expr {
 		block: CommandBlock,
 		pos: SourcePos,
 	},
+	/// Interpolated string. String parts are represented as string literals, and the
+	/// other parts are converted as in std.to_string.
+	Interpolation {
+		parts: Box<[Expr]>,
+		pos: SourcePos,
+	},
 }
 
 
@@ -206,20 +319,36 @@ pub enum Statement {
 		left: Lvalue,
 		right: Expr,
 	},
+	/// Assignment to the bindings of a pattern. Panics if the value doesn't match.
+	Destructure {
+		pattern: Pattern,
+		right: Expr,
+		pos: SourcePos,
+	},
+	/// Assignment combined with a binary operator. The l-value is evaluated only once.
+	CompoundAssign {
+		left: Lvalue,
+		op: BinaryOp,
+		right: Expr,
+		pos: SourcePos,
+	},
 	Return {
 		expr: Expr,
 	},
 	Break,
+	Continue,
 	/// While loop.
 	While {
 		condition: Expr,
 		block: Block,
 	},
-	/// For loop. Also introduces an identifier.
+	/// For loop. Also introduces the identifiers bound by the pattern. Panics if an
+	/// iterated value doesn't match the pattern.
 	For {
-		slot_ix: mem::SlotIx,
+		pattern: Pattern,
 		expr: Expr,
 		block: Block,
+		pos: SourcePos,
 	},
 	Expr(Expr),
 }
@@ -234,4 +363,6 @@ pub struct Program {
 	pub statements: Block,
 	/// How many slots in the root scope.
 	pub root_slots: mem::SlotIx,
+	/// Warnings produced by the static analysis.
+	pub warnings: Warnings,
 }
@@ -162,6 +162,14 @@ impl From<Frame> for FrameInfo {
 }
 
 
+/// A snapshot of the root scope of a frame.
+#[derive(Debug)]
+pub struct Checkpoint {
+	slots: SlotIx,
+	variables: HashMap<Symbol, SlotIx>,
+}
+
+
 /// A function scope stack.
 #[derive(Debug, Default)]
 pub struct Stack {
@@ -274,6 +282,37 @@ impl Stack {
 	}
 
 
+	/// Get the number of slots in the current frame.
+	pub fn slots(&self) -> SlotIx {
+		self.frames.last().expect("empty stack").slots
+	}
+
+
+	/// Take a snapshot of the root scope of the current frame.
+	/// Panics if the stack is empty.
+	pub fn checkpoint(&self) -> Checkpoint {
+		let frame = self.frames.last().expect("empty stack");
+		let scope = frame.scopes.first().expect("frame missing root scope");
+
+		Checkpoint {
+			slots: frame.slots,
+			variables: scope.variables.clone(),
+		}
+	}
+
+
+	/// Rollback the root scope of the current frame to the given snapshot.
+	/// Panics if the stack is empty.
+	pub fn restore(&mut self, checkpoint: Checkpoint) {
+		let frame = self.top();
+		frame.slots = checkpoint.slots;
+		frame.scopes
+			.first_mut()
+			.expect("frame missing root scope")
+			.variables = checkpoint.variables;
+	}
+
+
 	/// Get the top frame in the stack.
 	fn top(&mut self) -> &mut Frame {
 		self.frames.last_mut().expect("empty stack")
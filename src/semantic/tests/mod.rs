@@ -4,7 +4,13 @@ use std::{
 	os::unix::ffi::OsStrExt,
 };
 
-use crate::{fmt, semantic::ErrorsDisplayContext, symbol, syntax::{self, AnalysisDisplayContext}, tests};
+use crate::{
+	fmt,
+	semantic::ErrorsDisplayContext,
+	symbol,
+	syntax::{self, AnalysisDisplayContext, SourceMap},
+	tests,
+};
 use super::{program, Analyzer, Program, Errors};
 
 
@@ -20,6 +26,8 @@ where
 		move |path, file| {
 			let path_symbol = interner.get_or_intern(path.as_os_str().as_bytes());
 			let source = syntax::Source::from_reader(path_symbol, file)?;
+			let mut sources = SourceMap::default();
+			sources.insert(&source);
 			let syntactic_analysis = syntax::Analysis::analyze(&source, &mut interner);
 
 			if !syntactic_analysis.errors.is_empty() {
@@ -30,6 +38,7 @@ where
 						AnalysisDisplayContext {
 							max_errors: None,
 							interner: &interner,
+							sources: &sources,
 						}
 					)
 				);
@@ -54,6 +63,7 @@ where
 							ErrorsDisplayContext {
 								max_errors: None,
 								interner: &interner,
+								sources: &sources,
 							}
 						)
 					),
@@ -91,3 +101,12 @@ fn test_negative() -> io::Result<()> {
 		Result::is_err,
 	)
 }
+
+
+#[test]
+fn test_warning() -> io::Result<()> {
+	test_dir(
+		"src/semantic/tests/data/warning",
+		|result| matches!(result, Ok(program) if !program.warnings.0.is_empty()),
+	)
+}
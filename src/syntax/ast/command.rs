@@ -95,6 +95,8 @@ pub enum RedirectionTarget {
 	Overwrite(Argument),
 	/// Append to a file.
 	Append(Argument),
+	/// Close the file descriptor.
+	Close,
 }
 
 
@@ -104,12 +106,16 @@ pub enum Redirection {
 	/// An ill-formed redirection, produced by a parse error.
 	IllFormed,
 	/// Redirect output to a file or file descriptor.
+	/// Input duplication (`N<&M`) is equivalent to output duplication, and is also
+	/// represented by this variant.
 	Output {
 		source: FileDescriptor,
 		target: RedirectionTarget,
 	},
 	/// Redirect input from a file or literal.
 	Input {
+		/// The file descriptor to be redirected.
+		target: FileDescriptor,
 		/// Whether the source is the input or the file path.
 		literal: bool,
 		source: Argument,
@@ -211,6 +217,7 @@ pub enum CommandBlockKind {
 	Synchronous,  // {}
 	Asynchronous, // &{}
 	Capture,      // ${}
+	Stream,       // |{}
 }
 
 
@@ -220,6 +227,7 @@ impl CommandBlockKind {
 			lexer::TokenKind::Command => Some(Self::Synchronous),
 			lexer::TokenKind::AsyncCommand => Some(Self::Asynchronous),
 			lexer::TokenKind::CaptureCommand => Some(Self::Capture),
+			lexer::TokenKind::StreamCommand => Some(Self::Stream),
 			_ => None,
 		}
 	}
@@ -16,9 +16,14 @@ use super::{
 	Expr,
 	IllFormed,
 	Literal,
+	MatchArm,
+	Param,
+	ParamKind,
+	Pattern,
 	Redirection,
 	RedirectionTarget,
 	Statement,
+	TypeName,
 	UnaryOp,
 };
 use crate::{
@@ -163,7 +168,7 @@ impl<'a> Display<'a> for Literal {
 				fmt::sep_by(
 					params.iter(),
 					f,
-					|(ident, _), f| ident.fmt(f, context.interner),
+					|param, f| param.fmt(f, context.inlined()),
 					", "
 				)?;
 
@@ -186,6 +191,114 @@ impl<'a> Display<'a> for Literal {
 }
 
 
+impl<'a> Display<'a> for Param {
+	type Context = Context<'a>;
+
+	fn fmt(&self, f: &mut std::fmt::Formatter, context: Self::Context) -> std::fmt::Result {
+		if let ParamKind::Rest = self.kind {
+			"...".fmt(f)?;
+		}
+
+		self.identifier.fmt(f, context.interner)?;
+
+		if let ParamKind::Default(expr) = &self.kind {
+			" = ".fmt(f)?;
+			expr.fmt(f, context)?;
+		}
+
+		Ok(())
+	}
+}
+
+
+impl<'a> Display<'a> for TypeName {
+	type Context = &'a symbol::Interner;
+
+	fn fmt(&self, f: &mut std::fmt::Formatter, context: Self::Context) -> std::fmt::Result {
+		match self {
+			Self::Nil => color::Fg(color::Blue, "nil").fmt(f),
+			Self::Function => Keyword::Function.fmt(f),
+			Self::Named(symbol) => symbol.fmt(f, context),
+		}
+	}
+}
+
+
+impl<'a> Display<'a> for Pattern {
+	type Context = Context<'a>;
+
+	fn fmt(&self, f: &mut std::fmt::Formatter, context: Self::Context) -> std::fmt::Result {
+		match self {
+			Self::IllFormed => ILL_FORMED.fmt(f),
+
+			Self::Literal { literal, .. } => literal.fmt(f, context),
+
+			Self::Binding { identifier, .. } => identifier.fmt(f, context.interner),
+
+			Self::Type { pattern, type_name, .. } => {
+				pattern.fmt(f, context)?;
+				": ".fmt(f)?;
+				type_name.fmt(f, context.interner)
+			}
+
+			Self::Array { items, .. } => {
+				"[".fmt(f)?;
+				fmt::sep_by(items.iter(), f, |item, f| item.fmt(f, context), ", ")?;
+				"]".fmt(f)
+			}
+
+			Self::Dict { items, .. } => {
+				"@[".fmt(f)?;
+
+				fmt::sep_by(
+					items.iter(),
+					f,
+					|((key, _), value), f| {
+						key.fmt(f, context.interner)?;
+						": ".fmt(f)?;
+						value.fmt(f, context)
+					},
+					", "
+				)?;
+
+				"]".fmt(f)
+			}
+		}
+	}
+}
+
+
+impl<'a> Display<'a> for MatchArm {
+	type Context = Context<'a>;
+
+	fn fmt(&self, f: &mut std::fmt::Formatter, context: Self::Context) -> std::fmt::Result {
+		Keyword::Case.fmt(f)?;
+		" ".fmt(f)?;
+		self.pattern.fmt(f, context.inlined())?;
+
+		if let Some(guard) = &self.guard {
+			" ".fmt(f)?;
+			Keyword::If.fmt(f)?;
+			" ".fmt(f)?;
+			guard.fmt(f, context.inlined())?;
+		}
+
+		" ".fmt(f)?;
+		Keyword::Then.fmt(f)?;
+
+		if !self.block.is_empty() {
+			if context.indentation.is_some() {
+				"\n".fmt(f)?;
+			}
+
+			self.block.fmt(f, context.indent())?;
+		}
+
+		Ok(())
+	}
+}
+
+
 impl std::fmt::Display for UnaryOp {
 	fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
 		match self {
@@ -204,7 +317,14 @@ impl std::fmt::Display for BinaryOp {
 			Self::Minus => Operator::Minus.fmt(f),
 			Self::Times => Operator::Times.fmt(f),
 			Self::Div => Operator::Div.fmt(f),
+			Self::IntDiv => Operator::IntDiv.fmt(f),
 			Self::Mod => Operator::Mod.fmt(f),
+			Self::Pow => Operator::Pow.fmt(f),
+			Self::BitAnd => Operator::BitAnd.fmt(f),
+			Self::BitOr => Operator::BitOr.fmt(f),
+			Self::BitXor => Operator::BitXor.fmt(f),
+			Self::ShiftLeft => Operator::ShiftLeft.fmt(f),
+			Self::ShiftRight => Operator::ShiftRight.fmt(f),
 			Self::Equals => Operator::Equals.fmt(f),
 			Self::NotEquals => Operator::NotEquals.fmt(f),
 			Self::Greater => Operator::Greater.fmt(f),
@@ -296,6 +416,24 @@ impl<'a> Display<'a> for Expr {
 				Keyword::End.fmt(f)
 			}
 
+			Self::Match { value, arms, .. } => {
+				Keyword::Match.fmt(f)?;
+				" ".fmt(f)?;
+				value.fmt(f, context.inlined())?;
+				" ".fmt(f)?;
+				Keyword::With.fmt(f)?;
+
+				let nested = context.indent();
+
+				for arm in arms.iter() {
+					step(f, nested)?;
+					arm.fmt(f, nested)?;
+				}
+
+				step(f, context)?;
+				Keyword::End.fmt(f)
+			}
+
 			Self::Access { object, field, .. }
 			if matches!(field.as_ref(), Self::Literal { literal: Literal::Identifier(..), .. }) => {
 				object.fmt(f, context.inlined())?;
@@ -325,6 +463,26 @@ impl<'a> Display<'a> for Expr {
 			}
 
 			Self::CommandBlock { block, .. } => block.fmt(f, context),
+
+			Self::Interpolation { parts, .. } => {
+				"\"".fmt(f)?;
+
+				for part in parts.iter() {
+					match part {
+						Self::Literal { literal: Literal::String(s), .. } => {
+							color::Bold(String::from_utf8_lossy(s).escape_debug()).fmt(f)?
+						}
+
+						expr => {
+							"${".fmt(f)?;
+							expr.fmt(f, context.inlined())?;
+							"}".fmt(f)?;
+						}
+					}
+				}
+
+				"\"".fmt(f)
+			}
 		}
 	}
 }
@@ -345,12 +503,26 @@ impl<'a> Display<'a> for Statement {
 				init.fmt(f, context)
 			}
 
+			Self::Destructure { pattern, init, .. } => {
+				Keyword::Let.fmt(f)?;
+				" ".fmt(f)?;
+				pattern.fmt(f, context.inlined())?;
+				" = ".fmt(f)?;
+				init.fmt(f, context)
+			}
+
 			Self::Assign { left, right, .. } => {
 				left.fmt(f, context.inlined())?;
 				" = ".fmt(f)?;
 				right.fmt(f, context)
 			}
 
+			Self::CompoundAssign { left, op, right, .. } => {
+				left.fmt(f, context.inlined())?;
+				write!(f, " {}= ", op)?;
+				right.fmt(f, context)
+			}
+
 			Self::Return { expr, .. } => {
 				Keyword::Return.fmt(f)?;
 				" ".fmt(f)?;
@@ -359,6 +531,8 @@ impl<'a> Display<'a> for Statement {
 
 			Self::Break { .. } => Keyword::Break.fmt(f),
 
+			Self::Continue { .. } => Keyword::Continue.fmt(f),
+
 			Self::While { condition, block, .. } => {
 				let step = if context.indentation.is_some() { "\n" } else { " " };
 
@@ -381,12 +555,12 @@ impl<'a> Display<'a> for Statement {
 				Keyword::End.fmt(f)
 			}
 
-			Self::For { identifier, expr, block, .. } => {
+			Self::For { pattern, expr, block, .. } => {
 				let step = if context.indentation.is_some() { "\n" } else { " " };
 
 				Keyword::For.fmt(f)?;
 				" ".fmt(f)?;
-				identifier.fmt(f, context.interner)?;
+				pattern.fmt(f, context.inlined())?;
 				" ".fmt(f)?;
 				Keyword::In.fmt(f)?;
 				" ".fmt(f)?;
@@ -511,7 +685,9 @@ impl<'a> Display<'a> for RedirectionTarget {
 
 	fn fmt(&self, f: &mut std::fmt::Formatter, context: Self::Context) -> std::fmt::Result {
 		match self {
-			Self::Fd(fd) => write!(f, ">{}", fd),
+			Self::Fd(fd) => write!(f, ">&{}", fd),
+
+			Self::Close => ">&-".fmt(f),
 
 			Self::Overwrite(arg) => {
 				">".fmt(f)?;
@@ -539,12 +715,14 @@ impl<'a> Display<'a> for Redirection {
 				target.fmt(f, context)
 			}
 
-			Self::Input { literal: false, source } => {
+			Self::Input { target, literal: false, source } => {
+				target.fmt(f)?;
 				"<".fmt(f)?;
 				source.fmt(f, context)
 			}
 
-			Self::Input { literal: true, source } => {
+			Self::Input { target, literal: true, source } => {
+				target.fmt(f)?;
 				"<<".fmt(f)?;
 				source.fmt(f, context)
 			}
@@ -610,6 +788,7 @@ impl std::fmt::Display for CommandBlockKind {
 			Self::Synchronous => "{",
 			Self::Asynchronous => "&{",
 			Self::Capture => "${",
+			Self::Stream => "|{",
 		}.fmt(f)
 	}
 }
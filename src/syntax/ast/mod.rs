@@ -55,7 +55,7 @@ where
 
 impl IllFormed for SourcePos {
 	fn ill_formed() -> Self {
-		Self { line: 0, column: 0, path: Symbol::default() }
+		Self { line: 0, column: 0, path: Symbol::default(), len: 0 }
 	}
 
 	fn is_ill_formed(&self) -> bool {
@@ -115,6 +115,42 @@ impl IllFormed for Block {
 }
 
 
+/// The kind of a function parameter.
+#[derive(Debug)]
+pub enum ParamKind {
+	/// A parameter which must be supplied.
+	Required,
+	/// A parameter which is assigned the given expression when not supplied.
+	Default(Expr),
+	/// A trailing parameter which collects the extra arguments in an array.
+	Rest,
+}
+
+
+/// A function parameter.
+#[derive(Debug)]
+pub struct Param {
+	pub identifier: Symbol,
+	pub kind: ParamKind,
+	pub pos: SourcePos,
+}
+
+
+impl IllFormed for Param {
+	fn ill_formed() -> Self {
+		Self {
+			identifier: Symbol::ill_formed(),
+			kind: ParamKind::Required,
+			pos: SourcePos::ill_formed(),
+		}
+	}
+
+	fn is_ill_formed(&self) -> bool {
+		self.identifier.is_ill_formed()
+	}
+}
+
+
 /// Literals of all types in the language.
 /// Note that there are no literals for the error type.
 #[derive(Debug)]
@@ -128,8 +164,8 @@ pub enum Literal {
 	Array(Box<[Expr]>),
 	Dict(Box<[((Symbol, SourcePos), Expr)]>),
 	Function {
-		/// A list of parameters (identifiers).
-		params: Box<[(Symbol, SourcePos)]>,
+		/// A list of parameters.
+		params: Box<[Param]>,
 		body: Block,
 	},
 	/// For the dot access operator, we want to be able to have identifiers as literal
@@ -161,6 +197,73 @@ impl From<lexer::Literal> for Literal {
 }
 
 
+/// Type names in type patterns. Some type names are keywords, and therefore can't be
+/// represented as symbols.
+#[derive(Debug)]
+pub enum TypeName {
+	Nil,
+	Function,
+	Named(Symbol),
+}
+
+
+/// Patterns for match expressions.
+#[derive(Debug)]
+pub enum Pattern {
+	/// An ill-formed pattern, produced by a parse error.
+	IllFormed,
+	/// Matches values equal to the literal. Only basic literals are allowed.
+	Literal {
+		literal: Literal,
+		pos: SourcePos,
+	},
+	/// Matches any value, binding it to the identifier. The `_` identifier is a wildcard,
+	/// and introduces no binding.
+	Binding {
+		identifier: Symbol,
+		pos: SourcePos,
+	},
+	/// Matches values of the given type which also match the inner pattern.
+	Type {
+		pattern: Box<Pattern>,
+		type_name: TypeName,
+		pos: SourcePos,
+	},
+	/// Matches arrays of the same length whose items match the inner patterns.
+	Array {
+		items: Box<[Pattern]>,
+		pos: SourcePos,
+	},
+	/// Matches dicts containing the given keys whose values match the inner patterns.
+	Dict {
+		items: Box<[((Symbol, SourcePos), Pattern)]>,
+		pos: SourcePos,
+	},
+}
+
+
+impl IllFormed for Pattern {
+	fn ill_formed() -> Self {
+		Self::IllFormed
+	}
+
+	fn is_ill_formed(&self) -> bool {
+		matches!(self, Self::IllFormed)
+	}
+}
+
+
+/// An arm of a match expression.
+#[derive(Debug)]
+pub struct MatchArm {
+	pub pattern: Pattern,
+	/// An optional condition, which is checked after the pattern matches.
+	pub guard: Option<Expr>,
+	pub block: Block,
+	pub pos: SourcePos,
+}
+
+
 /// Unary operators.
 #[derive(Debug)]
 pub enum UnaryOp {
@@ -195,11 +298,19 @@ impl From<lexer::Operator> for UnaryOp {
 /// statements/expressions instead.
 #[derive(Debug)]
 pub enum BinaryOp {
-	Plus,  // +
-	Minus, // -
-	Times, // *
-	Div,   // /
-	Mod,   // %
+	Plus,   // +
+	Minus,  // -
+	Times,  // *
+	Div,    // /
+	IntDiv, // //
+	Mod,    // %
+	Pow,    // **
+
+	BitAnd,     // &
+	BitOr,      // |
+	BitXor,     // ^
+	ShiftLeft,  // <<
+	ShiftRight, // >>
 
 	Equals,        // ==
 	NotEquals,     // !=
@@ -223,7 +334,14 @@ impl From<lexer::Operator> for BinaryOp {
 			lexer::Operator::Minus => BinaryOp::Minus,
 			lexer::Operator::Times => BinaryOp::Times,
 			lexer::Operator::Div => BinaryOp::Div,
+			lexer::Operator::IntDiv => BinaryOp::IntDiv,
 			lexer::Operator::Mod => BinaryOp::Mod,
+			lexer::Operator::Pow => BinaryOp::Pow,
+			lexer::Operator::BitAnd => BinaryOp::BitAnd,
+			lexer::Operator::BitOr => BinaryOp::BitOr,
+			lexer::Operator::BitXor => BinaryOp::BitXor,
+			lexer::Operator::ShiftLeft => BinaryOp::ShiftLeft,
+			lexer::Operator::ShiftRight => BinaryOp::ShiftRight,
 			lexer::Operator::Equals => BinaryOp::Equals,
 			lexer::Operator::NotEquals => BinaryOp::NotEquals,
 			lexer::Operator::Greater => BinaryOp::Greater,
@@ -274,6 +392,12 @@ pub enum Expr {
 		otherwise: Block,
 		pos: SourcePos,
 	},
+	/// Match expression. The first arm whose pattern and guard match is executed.
+	Match {
+		value: Box<Expr>,
+		arms: Box<[MatchArm]>,
+		pos: SourcePos,
+	},
 	/// Field access ([]) operator.
 	Access {
 		object: Box<Expr>,
@@ -290,6 +414,11 @@ pub enum Expr {
 		block: CommandBlock,
 		pos: SourcePos,
 	},
+	/// Interpolated string literal. String parts are represented as string literals.
+	Interpolation {
+		parts: Box<[Expr]>,
+		pos: SourcePos,
+	},
 }
 
 
@@ -315,11 +444,24 @@ pub enum Statement {
 		init: Expr,
 		pos: SourcePos,
 	},
+	/// Introduces the identifiers bound by an array or dict pattern.
+	Destructure {
+		pattern: Pattern,
+		init: Expr,
+		pos: SourcePos,
+	},
 	Assign {
 		left: Expr,
 		right: Expr,
 		pos: SourcePos,
 	},
+	/// Assignment combined with a binary operator (+=, -=, *=, /=, ++=).
+	CompoundAssign {
+		left: Expr,
+		op: BinaryOp,
+		right: Expr,
+		pos: SourcePos,
+	},
 	Return {
 		expr: Expr,
 		pos: SourcePos,
@@ -327,15 +469,18 @@ pub enum Statement {
 	Break {
 		pos: SourcePos,
 	},
+	Continue {
+		pos: SourcePos,
+	},
 	/// While loop.
 	While {
 		condition: Expr,
 		block: Block,
 		pos: SourcePos,
 	},
-	/// For loop. Also introduces an identifier.
+	/// For loop. Also introduces the identifiers bound by the pattern.
 	For {
-		identifier: Symbol,
+		pattern: Pattern,
 		expr: Expr,
 		block: Block,
 		pos: SourcePos,
@@ -2,6 +2,7 @@ use super::{Error, Errors, AnalysisDisplayContext};
 use crate::{
 	fmt::{self, Display},
 	symbol,
+	syntax::Snippet,
 	term::color
 };
 
@@ -44,12 +45,18 @@ impl<'a> Display<'a> for Errors {
 				}
 			}
 
-			writeln!(
+			write!(
 				f,
 				"{}: {}",
 				color::Fg(color::Red, "Error"),
 				fmt::Show(error, context.interner)
 			)?;
+
+			if let Some(pos) = error.pos() {
+				write!(f, "{}", fmt::Show(Snippet(pos), context.sources))?;
+			}
+
+			writeln!(f)?;
 		}
 
 		Ok(())
@@ -1,6 +1,6 @@
 mod fmt;
 
-use super::{lexer, parser, AnalysisDisplayContext};
+use super::{lexer, parser, AnalysisDisplayContext, SourcePos};
 
 
 /// Syntax error.
@@ -11,6 +11,17 @@ pub enum Error {
 }
 
 
+impl Error {
+	/// The position where the error occurred, if known.
+	pub fn pos(&self) -> Option<SourcePos> {
+		match self {
+			Self::Lexer(error) => Some(error.pos),
+			Self::Parser(error) => error.pos(),
+		}
+	}
+}
+
+
 impl std::error::Error for Error {}
 
 
@@ -24,4 +35,19 @@ impl Errors {
 	pub fn is_empty(&self) -> bool {
 		self.0.is_empty()
 	}
+
+
+	/// Check if any of the errors was caused by premature end of input, meaning the source
+	/// might become valid if more input is provided.
+	pub fn is_incomplete(&self) -> bool {
+		self.0
+			.iter()
+			.any(
+				|error| matches!(
+					error,
+					Error::Lexer(lexer::Error { error: lexer::ErrorKind::UnexpectedEof, .. })
+						| Error::Parser(parser::Error::UnexpectedEof)
+				)
+			)
+	}
 }
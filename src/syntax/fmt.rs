@@ -1,4 +1,4 @@
-use super::{ast, Analysis};
+use super::{ast, Analysis, SourceMap};
 use crate::{
 	fmt::Display,
 	symbol,
@@ -12,6 +12,8 @@ pub struct AnalysisDisplayContext<'a> {
 	pub max_errors: Option<usize>,
 	/// Symbol interner.
 	pub interner: &'a symbol::Interner,
+	/// Source code for snippets.
+	pub sources: &'a SourceMap,
 }
 
 
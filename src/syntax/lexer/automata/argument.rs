@@ -7,6 +7,7 @@ use super::{
 	Cursor,
 	Checkpoint,
 	Error,
+	Escape,
 	SourcePos,
 	State,
 	SymbolInterner,
@@ -55,15 +56,30 @@ where
 	pub fn visit(mut self, cursor: &Cursor) -> Transition {
 		match (&self, cursor.peek()) {
 			// Escaped character.
-			(&Self { escaping: Some((offset, pos)), .. }, Some(value)) => {
-				if let Some(c) = C::validate_escape(value) {
-					self.value.push(c);
-					self.escaping = None;
-					Transition::step(self)
-				} else {
+			(&Self { escaping: Some((offset, pos)), .. }, Some(_)) => {
+				let escape_sequence = &cursor.slice()[offset ..= cursor.offset()];
+
+				match Escape::visit(escape_sequence, C::validate_escape) {
+					Escape::Incomplete => Transition::step(self),
+
 					// Invalid escape sequence.
-					let escape_sequence = &cursor.slice()[offset ..= cursor.offset()];
-					Transition::error(self, Error::invalid_escape_sequence(escape_sequence, pos))
+					Escape::Invalid => {
+						self.escaping = None;
+						Transition::error(self, Error::invalid_escape_sequence(escape_sequence, pos))
+					}
+
+					// Don't consume the character, which may be the closing quote.
+					Escape::Unterminated => {
+						self.escaping = None;
+						let escape_sequence = &escape_sequence[.. escape_sequence.len() - 1];
+						Transition::resume_error(self, Error::invalid_escape_sequence(escape_sequence, pos))
+					}
+
+					escape => {
+						self.escaping = None;
+						escape.push_to(&mut self.value);
+						Transition::step(self)
+					}
 				}
 			}
 
@@ -392,6 +408,9 @@ impl WordContext for SingleQuoted {
 			// Additional escape sequences:
 			b'n' => Some(b'\n'),
 			b't' => Some(b'\t'),
+			b'r' => Some(b'\r'),
+			b'e' => Some(b'\x1b'),
+			b'a' => Some(b'\x07'),
 			b'0' => Some(b'\0'),
 			b'\\' => Some(b'\\'),
 
@@ -481,6 +500,9 @@ impl WordContext for DoubleQuoted {
 			// Additional escape sequences:
 			b'n' => Some(b'\n'),
 			b't' => Some(b'\t'),
+			b'r' => Some(b'\r'),
+			b'e' => Some(b'\x1b'),
+			b'a' => Some(b'\x07'),
 			b'0' => Some(b'\0'),
 			b'\\' => Some(b'\\'),
 
@@ -611,6 +633,9 @@ impl WordContext for Argument {
 			// Additional escape sequences:
 			b'n' => Some(b'\n'),
 			b't' => Some(b'\t'),
+			b'r' => Some(b'\r'),
+			b'e' => Some(b'\x1b'),
+			b'a' => Some(b'\x07'),
 			b'0' => Some(b'\0'),
 			b'\\' => Some(b'\\'),
 
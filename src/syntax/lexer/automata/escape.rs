@@ -0,0 +1,79 @@
+/// The result of visiting a character in an escape sequence.
+#[derive(Debug)]
+pub(super) enum Escape {
+	/// The escape sequence is not finished yet.
+	Incomplete,
+	/// The escape sequence stands for a single byte.
+	Byte(u8),
+	/// The escape sequence stands for a unicode character, to be encoded as UTF-8.
+	Char(char),
+	/// Invalid escape sequence.
+	Invalid,
+	/// Invalid escape sequence which ends before the last visited character. That
+	/// character is not part of the sequence, and must be lexed on its own.
+	Unterminated,
+}
+
+
+impl Escape {
+	/// Check a (possibly incomplete) escape sequence, including the leading backslash.
+	/// The last character of the sequence is the one being visited.
+	/// Multi-character escapes (\xNN and \u{X...}) are handled here, while single
+	/// character escapes are delegated to the given validation function.
+	pub fn visit<F>(sequence: &[u8], validate: F) -> Self
+	where
+		F: FnOnce(u8) -> Option<u8>,
+	{
+		let is_hex = |digits: &[u8]| digits.iter().all(u8::is_ascii_hexdigit);
+
+		match sequence {
+			// Byte escape.
+			[b'\\', b'x', digits @ ..] if digits.len() < 2 && is_hex(digits) => Self::Incomplete,
+			[b'\\', b'x', digits @ ..] if digits.len() == 2 && is_hex(digits) => {
+				Self::Byte(parse_hex(digits) as u8)
+			}
+
+			// Unicode escape.
+			[b'\\', b'u'] => Self::Incomplete,
+			[b'\\', b'u', b'{', digits @ .., b'}'] if !digits.is_empty() && digits.len() <= 6 && is_hex(digits) => {
+				char::from_u32(parse_hex(digits))
+					.map(Self::Char)
+					.unwrap_or(Self::Invalid)
+			}
+			[b'\\', b'u', b'{', digits @ ..] if digits.len() <= 6 && is_hex(digits) => Self::Incomplete,
+
+			[b'\\', b'u', b'{', .., b'}'] => Self::Invalid,
+			[b'\\', b'x', ..] | [b'\\', b'u', ..] => Self::Unterminated,
+
+			// Single character escape.
+			[b'\\', value] => validate(*value)
+				.map(Self::Byte)
+				.unwrap_or(Self::Invalid),
+
+			_ => Self::Invalid,
+		}
+	}
+
+
+	/// Append the escaped value to the given buffer.
+	/// Panics if the escape sequence is not complete.
+	pub fn push_to(self, value: &mut Vec<u8>) {
+		match self {
+			Self::Byte(byte) => value.push(byte),
+			Self::Char(c) => value.extend_from_slice(c.encode_utf8(&mut [0; 4]).as_bytes()),
+			_ => panic!("escape sequence is not complete"),
+		}
+	}
+}
+
+
+/// Parse a sequence of hex digits. The digits must have been validated, and must fit in
+/// an u32.
+fn parse_hex(digits: &[u8]) -> u32 {
+	digits
+		.iter()
+		.fold(
+			0,
+			|value, digit| value * 16 + (*digit as char).to_digit(16).expect("invalid hex digit")
+		)
+}
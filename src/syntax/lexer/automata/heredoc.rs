@@ -0,0 +1,380 @@
+use super::{
+	argument::{Dollar, DollarContext},
+	word::IsWord,
+	ArgPart,
+	ArgUnit,
+	Checkpoint,
+	Command,
+	Cursor,
+	Error,
+	Root,
+	SourcePos,
+	State,
+	Token,
+	TokenKind,
+	Transition,
+};
+use crate::symbol::Symbol;
+
+
+/// The current phase of a heredoc.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Phase {
+	/// Skipping the rest of the header line, after the delimiter.
+	Header,
+	/// At the start of a body line, which may be the terminator.
+	LineStart,
+	/// Stripping the given amount of indentation characters.
+	Indentation(usize),
+	/// Inside a body line.
+	Body,
+	/// Skipping the terminator line.
+	Terminator,
+}
+
+
+/// The state for lexing heredocs in command blocks, after the `<<` operator.
+/// Heredocs have the form `<<DELIMITER`, followed by the body lines and a line with the
+/// delimiter. Dollar identifiers are expanded unless the delimiter is enclosed in single
+/// quotes. If the delimiter is prefixed by a dash, the common indentation is stripped,
+/// and the terminator line may be indented.
+/// The header line may continue after the delimiter, e.g. with a pipe or a redirection,
+/// in which case the rest of the line is lexed after the body.
+#[derive(Debug)]
+pub(super) struct Heredoc {
+	/// The delimiter which terminates the heredoc.
+	delimiter: Box<[u8]>,
+	/// Whether to expand dollar identifiers.
+	expand: bool,
+	/// Whether to strip the common indentation of the body lines.
+	strip: bool,
+	/// How many indentation characters to strip from each line.
+	indentation: usize,
+	/// The current phase.
+	phase: Phase,
+	/// Whether the next character is escaped.
+	escaping: bool,
+	/// The current literal, if any.
+	value: Vec<u8>,
+	/// The parts of the body.
+	parts: Vec<ArgUnit>,
+	/// The offset of the rest of the header line, after the delimiter.
+	rest_offset: usize,
+	/// The offset of the line break which ends the header line.
+	header_end: usize,
+	/// The position of the rest of the header line, if there is anything other than
+	/// whitespace.
+	rest: Option<Checkpoint>,
+	/// The position of the heredoc operator.
+	pos: SourcePos,
+}
+
+
+impl Heredoc {
+	/// Check if the input after the `<<` operator is a heredoc header. The header must be
+	/// followed by a line break, and the body must be terminated by the delimiter line.
+	/// Otherwise, the operator is a literal redirection. The cursor must be at the second
+	/// character of the operator.
+	pub fn header(cursor: &Cursor, pos: SourcePos) -> Option<Self> {
+		let input = cursor.slice().get(cursor.offset() + 1 ..)?;
+
+		let (strip, input) = match input {
+			[ b'-', input @ .. ] => (true, input),
+			input => (false, input),
+		};
+
+		let (expand, input) = match input {
+			[ b'\'', input @ .. ] => (false, input),
+			input => (true, input),
+		};
+
+		if !input.first()?.is_word_start() {
+			return None;
+		}
+
+		let length = input
+			.iter()
+			.take_while(|c| c.is_word())
+			.count();
+		let (delimiter, input) = input.split_at(length);
+
+		let input = match input {
+			[ b'\'', input @ .. ] if !expand => input,
+			_ if !expand => return None,
+			input => input,
+		};
+
+		let rest_offset = cursor.slice().len() - input.len();
+		let header_end = rest_offset + input.iter().position(|&c| c == b'\n')?;
+
+		// A header line that closes the enclosing block has no room for a body.
+		let mut depth = 0;
+		if !Self::keeps_block_open(&input[.. header_end - rest_offset], &mut depth) {
+			return None;
+		}
+
+		let heredoc = Self {
+			delimiter: delimiter.into(),
+			expand,
+			strip,
+			indentation: 0,
+			phase: Phase::Header,
+			escaping: false,
+			value: Vec::new(),
+			parts: Vec::new(),
+			rest_offset,
+			header_end,
+			rest: None,
+			pos,
+		};
+
+		// The terminator must be within the enclosing block, so that the search stops at
+		// the end of the block instead of scanning the rest of the source.
+		for line in cursor.slice()[header_end + 1 ..].split(|&c| c == b'\n') {
+			if heredoc.is_terminator(line) {
+				return Some(heredoc);
+			}
+
+			if !Self::keeps_block_open(line, &mut depth) {
+				return None;
+			}
+		}
+
+		None
+	}
+
+
+	/// Track the depth of the braces in the given line, relative to the enclosing block.
+	/// Returns false if the line closes the block.
+	fn keeps_block_open(line: &[u8], depth: &mut usize) -> bool {
+		for &c in line {
+			match c {
+				b'{' => *depth += 1,
+				b'}' if *depth == 0 => return false,
+				b'}' => *depth -= 1,
+				_ => (),
+			}
+		}
+
+		true
+	}
+
+
+	pub fn visit(mut self, cursor: &Cursor) -> Transition {
+		match (self.phase, cursor.peek()) {
+			// End of the header.
+			(Phase::Header, Some(b'\n')) => {
+				if self.strip {
+					self.indentation = self.common_indentation(cursor.offset() + 1, cursor.slice());
+				}
+
+				self.phase = Phase::LineStart;
+				Transition::step(self)
+			}
+
+			// Save the rest of the header line, to be lexed after the body.
+			(Phase::Header, Some(_)) if cursor.offset() == self.rest_offset => {
+				let rest = Self::line(self.rest_offset, cursor.slice());
+				if !rest.iter().all(u8::is_ascii_whitespace) {
+					self.rest = Some(cursor.checkpoint());
+				}
+
+				Transition::step(self)
+			}
+
+			(Phase::Header, Some(_)) => Transition::step(self),
+
+			// Start of a line.
+			(Phase::LineStart, Some(_)) => {
+				let line = Self::line(cursor.offset(), cursor.slice());
+
+				self.phase = if self.is_terminator(line) {
+					Phase::Terminator
+				} else {
+					Phase::Indentation(self.indentation)
+				};
+
+				Transition::resume(self)
+			}
+
+			// Indentation.
+			(Phase::Indentation(count), Some(b' ')) | (Phase::Indentation(count), Some(b'\t'))
+				if count > 0 => {
+				self.phase = Phase::Indentation(count - 1);
+				Transition::step(self)
+			}
+
+			(Phase::Indentation(_), Some(_)) => {
+				self.phase = Phase::Body;
+				Transition::resume(self)
+			}
+
+			// Escaped dollar.
+			(Phase::Body, Some(b'$')) if self.escaping => {
+				self.escaping = false;
+				self.value.push(b'$');
+				Transition::step(self)
+			}
+
+			(Phase::Body, Some(b'\\'))
+				if self.expand && cursor.slice().get(cursor.offset() + 1) == Some(&b'$') => {
+				self.escaping = true;
+				Transition::step(self)
+			}
+
+			// Dollar.
+			(Phase::Body, Some(b'$')) if self.expand => Transition::step(Dollar::at(cursor, self)),
+
+			// End of line.
+			(Phase::Body, Some(b'\n')) => {
+				self.value.push(b'\n');
+				self.phase = Phase::LineStart;
+				Transition::step(self)
+			}
+
+			(Phase::Body, Some(c)) => {
+				self.value.push(c);
+				Transition::step(self)
+			}
+
+			// End of the terminator line.
+			(Phase::Terminator, Some(b'\n')) | (Phase::Terminator, None) => self.produce(cursor),
+
+			(Phase::Terminator, Some(_)) => Transition::step(self),
+
+			// The last line may be the terminator, even if there's no trailing newline.
+			(Phase::LineStart, None) if self.is_terminator(b"") => self.produce(cursor),
+
+			// Eof.
+			(_, None) => Transition::error(Root, Error::unexpected_eof(cursor.pos())),
+		}
+	}
+
+
+	/// Get the line starting at the given offset, without the line break.
+	fn line(offset: usize, input: &[u8]) -> &[u8] {
+		let input = &input[offset ..];
+		let end = input
+			.iter()
+			.position(|&c| c == b'\n')
+			.unwrap_or(input.len());
+
+		&input[.. end]
+	}
+
+
+	/// Check if the given line terminates the heredoc. Trailing whitespace is ignored, and
+	/// so is leading whitespace if the indentation is stripped.
+	fn is_terminator(&self, line: &[u8]) -> bool {
+		let is_whitespace = |c: &u8| c.is_ascii_whitespace();
+		let start =
+			if self.strip {
+				line.iter().position(|c| !is_whitespace(c)).unwrap_or(line.len())
+			} else {
+				0
+			};
+		let end = line.iter().rposition(|c| !is_whitespace(c)).map_or(start, |end| end + 1);
+
+		line.get(start .. end) == Some(&self.delimiter)
+	}
+
+
+	/// Compute the common indentation of the non-blank body lines starting at the given
+	/// offset.
+	fn common_indentation(&self, offset: usize, input: &[u8]) -> usize {
+		input[offset ..]
+			.split(|&c| c == b'\n')
+			.take_while(|line| !self.is_terminator(line))
+			.filter(|line| !line.iter().all(u8::is_ascii_whitespace))
+			.map(
+				|line| line
+					.iter()
+					.take_while(|&&c| c == b' ' || c == b'\t')
+					.count()
+			)
+			.min()
+			.unwrap_or(0)
+	}
+
+
+	/// Produce the heredoc token, without consuming the end of the terminator line. If the
+	/// header line continues after the delimiter, the rest of it is lexed before resuming
+	/// after the terminator line.
+	fn produce(self, cursor: &Cursor) -> Transition {
+		let rest = self.rest;
+		let header_end = self.header_end;
+		let token = self.into_token();
+
+		match rest {
+			Some(rest) => Transition::detour_produce(Command, token, rest, header_end, cursor),
+			None => Transition::resume_produce(Command, token),
+		}
+	}
+
+
+	/// Push the current literal, if any, to the body parts.
+	fn push_literal(&mut self) {
+		if !self.value.is_empty() {
+			let value = std::mem::take(&mut self.value);
+			self.parts.push(ArgUnit::Literal(value.into_boxed_slice()));
+		}
+	}
+
+
+	/// Produce the argument token for the heredoc body. Heredocs behave as double quoted
+	/// arguments, and therefore are not subject to pattern expansions. The last line break
+	/// is omitted, as literal input redirection already terminates the input with one.
+	fn into_token(mut self) -> Token {
+		if self.value.last() == Some(&b'\n') {
+			self.value.pop();
+		}
+
+		self.push_literal();
+
+		Token {
+			kind: TokenKind::Argument(
+				vec![ ArgPart::DoubleQuoted(self.parts.into_boxed_slice()) ].into_boxed_slice()
+			),
+			pos: self.pos,
+		}
+	}
+}
+
+
+impl DollarContext for Heredoc {
+	fn produce(mut self, symbol: Symbol, pos: SourcePos) -> Transition {
+		self.push_literal();
+		self.parts.push(ArgUnit::Dollar { symbol, pos });
+
+		Transition::step(self)
+	}
+
+	fn error(self, error: Error) -> Transition {
+		Transition::error(self, error)
+	}
+
+	fn resume(mut self, symbol: Symbol, pos: SourcePos) -> Transition {
+		self.push_literal();
+		self.parts.push(ArgUnit::Dollar { symbol, pos });
+
+		Transition::resume(self)
+	}
+
+	fn resume_error(self, error: Error) -> Transition {
+		Transition::resume_error(self, error)
+	}
+}
+
+
+impl From<Heredoc> for State {
+	fn from(state: Heredoc) -> State {
+		Self::Heredoc(state)
+	}
+}
+
+
+impl From<Dollar<Heredoc>> for State {
+	fn from(state: Dollar<Heredoc>) -> State {
+		Self::HeredocDollar(state)
+	}
+}
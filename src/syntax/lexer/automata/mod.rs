@@ -1,7 +1,9 @@
 mod argument;
 mod command;
 mod comment;
+mod escape;
 mod expansion;
+mod heredoc;
 mod number;
 mod root;
 mod string;
@@ -13,6 +15,8 @@ use self::{
 	expansion::Expansion,
 	command::Command,
 	comment::Comment,
+	escape::Escape,
+	heredoc::Heredoc,
 	number::NumberLiteral,
 	root::Root,
 	string::{ByteLiteral, StringLiteral},
@@ -50,6 +54,10 @@ enum Step {
 	Forward,
 	/// Rollback to the given checkpoint.
 	Rollback(Checkpoint),
+	/// Rollback to the given checkpoint, and jump to the resume checkpoint once the
+	/// cursor reaches the given offset. This is used to lex the rest of a heredoc header
+	/// line after the body.
+	Detour { rollback: Checkpoint, until: usize, resume: Checkpoint },
 }
 
 
@@ -59,6 +67,7 @@ impl Step {
 			Self::Resume => (),
 			Self::Forward => cursor.step(),
 			Self::Rollback(checkpoint) => cursor.rollback(*checkpoint),
+			Self::Detour { rollback, .. } => cursor.rollback(*rollback),
 		}
 	}
 }
@@ -132,6 +141,22 @@ impl Transition {
 			output: None,
 		}
 	}
+
+	/// Produce a token, then rollback to a checkpoint with the given state. Once the
+	/// cursor reaches the given offset, it jumps to the current position.
+	pub fn detour_produce<S: Into<State>>(
+		state: S,
+		token: Token,
+		rollback: Checkpoint,
+		until: usize,
+		cursor: &Cursor,
+	) -> Self {
+		Self {
+			state: state.into(),
+			step: Step::Detour { rollback, until, resume: cursor.checkpoint() },
+			output: Some(Ok(token)),
+		}
+	}
 }
 
 
@@ -160,6 +185,8 @@ enum State {
 	DoubleQuotedWord(argument::Word<DoubleQuoted>),
 	Dollar(argument::Dollar<Argument>),
 	QuotedDollar(argument::Dollar<DoubleQuoted>),
+	Heredoc(Heredoc),
+	HeredocDollar(argument::Dollar<Heredoc>),
 	CommandSymbol(CommandSymbol),
 }
 
@@ -172,13 +199,18 @@ impl Default for State {
 
 
 impl State {
-	pub fn visit(self, cursor: &Cursor, interner: &mut SymbolInterner) -> Transition {
+	pub fn visit(
+		self,
+		cursor: &Cursor,
+		interner: &mut SymbolInterner,
+		interpolations: &mut usize,
+	) -> Transition {
 		match self {
-			Self::Root(state) => state.visit(cursor),
+			Self::Root(state) => state.visit(cursor, interpolations),
 			Self::Comment(state) => state.visit(cursor),
 			Self::NumberLiteral(state) => state.visit(cursor),
 			Self::ByteLiteral(state) => state.visit(cursor),
-			Self::StringLiteral(state) => state.visit(cursor),
+			Self::StringLiteral(state) => state.visit(cursor, interpolations),
 			Self::Word(state) => state.visit(cursor, interner),
 			Self::Symbol(state) => state.visit(cursor),
 
@@ -194,6 +226,8 @@ impl State {
 			Self::DoubleQuotedWord(state) => state.visit(cursor),
 			Self::Dollar(state) => state.visit(cursor, interner),
 			Self::QuotedDollar(state) => state.visit(cursor, interner),
+			Self::Heredoc(state) => state.visit(cursor),
+			Self::HeredocDollar(state) => state.visit(cursor, interner),
 			Self::CommandSymbol(state) => state.visit(cursor),
 		}
 	}
@@ -206,12 +240,46 @@ pub(super) struct Automata<'a, 'b> {
 	state: State,
 	cursor: Cursor<'a>,
 	interner: &'b mut SymbolInterner,
+	/// How many string interpolations are currently open.
+	interpolations: usize,
+	/// The offset at which the cursor must jump to the checkpoint, if any.
+	detour: Option<(usize, Checkpoint)>,
 }
 
 
 impl<'a, 'b> Automata<'a, 'b> {
 	pub fn new(cursor: Cursor<'a>, interner: &'b mut SymbolInterner) -> Self {
-		Self { state: State::default(), cursor, interner }
+		Self { state: State::default(), cursor, interner, interpolations: 0, detour: None }
+	}
+}
+
+
+impl<'a, 'b> Automata<'a, 'b> {
+	/// The length of a token starting at the given position and ending at the cursor,
+	/// limited to the token's first line.
+	fn token_len(&self, pos: SourcePos) -> u32 {
+		let end = self.cursor.pos();
+
+		if end.line == pos.line {
+			return end.column.saturating_sub(pos.column);
+		}
+
+		// Find the end of the token's first line by walking back over the following lines.
+		let input = &self.cursor.slice()[..self.cursor.offset()];
+		let mut newlines = input
+			.iter()
+			.enumerate()
+			.rev()
+			.filter(|(_, &c)| c == b'\n')
+			.map(|(ix, _)| ix);
+
+		let line_end = newlines.nth((end.line - pos.line - 1) as usize);
+		let line_start = newlines.next().map(|ix| ix + 1).unwrap_or(0);
+
+		line_end
+			.map(|line_end| (line_end - line_start) as u32)
+			.map(|line_len| line_len.saturating_sub(pos.column))
+			.unwrap_or(0)
 	}
 }
 
@@ -224,17 +292,39 @@ impl<'a, 'b> Iterator for Automata<'a, 'b> {
 			// We must temporarily take the state so that we can consume it.
 			let state = std::mem::take(&mut self.state);
 
-			let transition = state.visit(&self.cursor, self.interner);
+			let transition = state.visit(&self.cursor, self.interner, &mut self.interpolations);
 
 			self.state = transition.state;
 
 			// Check EOF *before* stepping.
 			let eof = self.cursor.is_eof();
 
+			if let Step::Detour { until, resume, .. } = transition.step {
+				self.detour = Some((until, resume));
+			}
+
 			transition.step.apply(&mut self.cursor);
 
-			if let Some(output) = transition.output {
-				return Some(output);
+			let mut output = transition.output;
+
+			match &mut output {
+				Some(Ok(Token { pos, .. })) | Some(Err(Error { pos, .. })) => {
+					pos.len = self.token_len(*pos);
+				}
+				None => (),
+			}
+
+			// The jump must happen between tokens, as states may slice the input from the
+			// start of their token.
+			if let Some((until, resume)) = self.detour {
+				if self.cursor.offset() == until && matches!(self.state, State::Root(_) | State::Command(_)) {
+					self.cursor.rollback(resume);
+					self.detour = None;
+				}
+			}
+
+			if output.is_some() {
+				return output;
 			}
 
 			if eof {
@@ -1,3 +1,5 @@
+use std::num::{IntErrorKind, ParseIntError};
+
 use super::{
 	Cursor,
 	Error,
@@ -13,11 +15,18 @@ use super::{
 
 
 /// The state for lexing numeric literals, both integer and float.
+/// Integers may be prefixed with a radix (0x, 0o, 0b), and digits may be separated by
+/// underscores (1_000).
 #[derive(Debug)]
 pub(super) struct NumberLiteral {
 	start_offset: usize,
+	/// The radix of prefixed integer literals.
+	radix: Option<u32>,
 	consumed_decimal: Option<bool>,
 	consumed_exponent: Option<bool>,
+	/// Whether a misplaced digit separator was found, in which case the rest of the
+	/// literal is consumed and reported as a whole.
+	invalid: bool,
 	pos: SourcePos,
 }
 
@@ -26,21 +35,66 @@ impl NumberLiteral {
 	pub fn at(cursor: &Cursor) -> Self {
 		Self {
 			start_offset: cursor.offset(),
+			radix: None,
 			consumed_decimal: None,
 			consumed_exponent: None,
+			invalid: false,
 			pos: cursor.pos(),
 		}
 	}
 
 
 	pub fn visit(mut self, cursor: &Cursor) -> Transition {
+		let radix = self.radix.unwrap_or(10);
+		// The first digit is consumed by the root state, so there is always a previous char.
+		let previous = cursor.slice()[cursor.offset() - 1];
+		let is_digit = |c: u8| (c as char).is_digit(radix);
+
+		// A digit separator must be preceded and followed by a digit.
+		let value = cursor.peek();
+		if (previous == b'_' && !value.is_some_and(is_digit)) || (value == Some(b'_') && !is_digit(previous)) {
+			self.invalid = true;
+		}
+
 		let error = |error| Transition::error(Root, Error { error, pos: self.pos });
 
-		match (&self, cursor.peek()) {
+		match (&self, value) {
+			// Consume the remainder of an invalid literal.
+			(&Self { invalid: true, .. }, Some(c)) if c.is_ascii_alphanumeric() || c == b'_' => {
+				Transition::step(self)
+			}
+
+			(&Self { invalid: true, .. }, _) => {
+				let number = &cursor.slice()[self.start_offset .. cursor.offset()];
+				Transition::resume_error(Root, Error::invalid_number(number, self.pos))
+			}
+
+			// Digit separator.
+			(_, Some(b'_')) => Transition::step(self),
+
+			// Radix prefix.
+			(
+				&Self {
+					radix: None, consumed_decimal: None, consumed_exponent: None, ..
+				},
+				Some(c),
+			) if cursor.offset() == self.start_offset + 1 && previous == b'0' && radix_of(c).is_some() => {
+				self.radix = radix_of(c);
+				Transition::step(self)
+			}
+
+			// Digits of prefixed literals.
+			(&Self { radix: Some(_), .. }, Some(c)) if is_digit(c) => Transition::step(self),
+
+			// Prefixed literals must not be followed by alphanumeric characters.
+			(&Self { radix: Some(radix), .. }, Some(c)) if c.is_ascii_alphanumeric() => {
+				error(ErrorKind::InvalidDigit { digit: c, radix })
+			}
+
 			// There must be up to one dot, and it must precede the exponent.
 			(
 				&Self {
-					consumed_decimal: None, consumed_exponent: None, ..
+					radix: None, consumed_decimal: None, consumed_exponent: None, ..
 				},
 				Some(b'.'),
 			) => {
@@ -49,13 +103,13 @@ impl NumberLiteral {
 			}
 
 			// Exponent may be present regardless of dot.
-			(&Self { consumed_exponent: None, .. }, Some(c)) if c == b'e' || c == b'E' => {
+			(&Self { radix: None, consumed_exponent: None, .. }, Some(c)) if c == b'e' || c == b'E' => {
 				self.consumed_exponent = Some(false);
 				Transition::step(self)
 			}
 
 			// Consume digits.
-			(_, Some(value)) if value.is_ascii_digit() => {
+			(&Self { radix: None, .. }, Some(value)) if value.is_ascii_digit() => {
 				// If a dot or an exponent preceded, then set the according flag.
 				if self.consumed_decimal == Some(false) {
 					self.consumed_decimal = Some(true);
@@ -93,10 +147,24 @@ impl NumberLiteral {
 		let literal = |literal| Ok(Token { kind: TokenKind::Literal(literal), pos: self.pos });
 
 		// There is no method in std to parse a number from a byte array.
-		let number_str = std::str::from_utf8(number)
-			.expect("number literals should be valid ascii, which should be valid utf8");
-
-		if self.is_float() {
+		let number_str: String = std::str::from_utf8(number)
+			.expect("number literals should be valid ascii, which should be valid utf8")
+			.chars()
+			.filter(|&c| c != '_')
+			.collect();
+
+		let int_error = |error: ParseIntError| match error.kind() {
+			IntErrorKind::PosOverflow => Error::integer_out_of_range(number, self.pos),
+			_ => Error::invalid_number(number, self.pos),
+		};
+
+		if let Some(radix) = self.radix {
+			// Skip the radix prefix.
+			match i64::from_str_radix(&number_str[2..], radix) {
+				Ok(int) => literal(Literal::Int(int)),
+				Err(error) => Err(int_error(error)),
+			}
+		} else if self.is_float() {
 			match number_str.parse() {
 				Ok(float) => literal(Literal::Float(float)),
 				Err(_) => Err(Error::invalid_number(number, self.pos)),
@@ -104,7 +172,7 @@ impl NumberLiteral {
 		} else {
 			match number_str.parse() {
 				Ok(int) => literal(Literal::Int(int)),
-				Err(_) => Err(Error::invalid_number(number, self.pos)),
+				Err(error) => Err(int_error(error)),
 			}
 		}
 	}
@@ -117,6 +185,17 @@ impl NumberLiteral {
 }
 
 
+/// Get the radix for the given prefix character, if any.
+fn radix_of(prefix: u8) -> Option<u32> {
+	match prefix {
+		b'x' => Some(16),
+		b'o' => Some(8),
+		b'b' => Some(2),
+		_ => None,
+	}
+}
+
+
 impl From<NumberLiteral> for State {
 	fn from(state: NumberLiteral) -> State {
 		Self::NumberLiteral(state)
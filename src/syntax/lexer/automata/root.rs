@@ -23,7 +23,7 @@ pub(super) struct Root;
 
 
 impl Root {
-	pub fn visit(self, cursor: &Cursor) -> Transition {
+	pub fn visit(self, cursor: &Cursor, interpolations: &mut usize) -> Transition {
 		match cursor.peek() {
 			// Whitespace.
 			Some(c) if c.is_ascii_whitespace() => Transition::step(self),
@@ -34,6 +34,12 @@ impl Root {
 			// String literals.
 			Some(b'"') => Transition::step(StringLiteral::at(cursor)),
 
+			// End of interpolated expression.
+			Some(b'}') if *interpolations > 0 => {
+				*interpolations -= 1;
+				Transition::step(StringLiteral::resume(cursor))
+			}
+
 			// Byte literals.
 			Some(b'\'') => Transition::step(ByteLiteral::at(cursor)),
 
@@ -1,4 +1,4 @@
-use super::{Cursor, Error, Literal, Root, SourcePos, State, Token, TokenKind, Transition};
+use super::{Cursor, Error, Escape, Literal, Root, SourcePos, State, Token, TokenKind, Transition};
 
 
 /// The state for lexing byte literals.
@@ -40,19 +40,41 @@ impl ByteLiteral {
 			}
 
 			// Escaped character.
-			(&Self { escaping: Some((offset, pos)), .. }, Some(value)) => {
-				self.escaping = None;
-
-				if let Some(c) = validate_escape(value) {
-					self.value = Some(c);
-					Transition::step(self)
-				} else {
-					// Use a placeholder to produce a valid literal after reporting the error. This
-					// won't get to be actually used, because the program won't be interpreted after
-					// parsing.
-					self.value = Some(b'\0');
-					let escape_sequence = &cursor.slice()[offset ..= cursor.offset()];
-					Transition::error(self, Error::invalid_escape_sequence(escape_sequence, pos))
+			(&Self { escaping: Some((offset, pos)), .. }, Some(_)) => {
+				let escape_sequence = &cursor.slice()[offset ..= cursor.offset()];
+
+				match Escape::visit(escape_sequence, validate_escape) {
+					Escape::Incomplete => Transition::step(self),
+
+					Escape::Byte(c) => {
+						self.escaping = None;
+						self.value = Some(c);
+						Transition::step(self)
+					}
+
+					// Char literals hold a single byte, so only ASCII characters are allowed.
+					Escape::Char(c) if c.is_ascii() => {
+						self.escaping = None;
+						self.value = Some(c as u8);
+						Transition::step(self)
+					}
+
+					Escape::Char(_) | Escape::Invalid => {
+						self.escaping = None;
+						// Use a placeholder to produce a valid literal after reporting the error. This
+						// won't get to be actually used, because the program won't be interpreted after
+						// parsing.
+						self.value = Some(b'\0');
+						Transition::error(self, Error::invalid_escape_sequence(escape_sequence, pos))
+					}
+
+					// Don't consume the character, which may be the closing quote.
+					Escape::Unterminated => {
+						self.escaping = None;
+						self.value = Some(b'\0');
+						let escape_sequence = &escape_sequence[.. escape_sequence.len() - 1];
+						Transition::resume_error(self, Error::invalid_escape_sequence(escape_sequence, pos))
+					}
 				}
 			}
 
@@ -85,12 +107,19 @@ impl From<ByteLiteral> for State {
 
 
 /// The state for lexing string literals.
+/// String literals may contain interpolated expressions (`${expr}`). In such case, the
+/// literal is split in multiple tokens, and the tokens of the expressions are produced
+/// by the root state in between.
 #[derive(Debug)]
 pub(super) struct StringLiteral {
 	/// The parsed bytes, if any.
 	value: Vec<u8>,
 	/// The position of the current escape sequence, if any.
 	escaping: Option<(usize, SourcePos)>,
+	/// Whether the previous character started an interpolation (`$` followed by `{`).
+	interpolating: bool,
+	/// Whether the literal is being resumed after an interpolated expression.
+	resumed: bool,
 	/// The position of the literal.
 	pos: SourcePos,
 }
@@ -101,29 +130,72 @@ impl StringLiteral {
 		Self {
 			value: Vec::with_capacity(8), // We expect most literals to not be empty.
 			escaping: None,
+			interpolating: false,
+			resumed: false,
 			pos: cursor.pos(),
 		}
 	}
 
 
-	pub fn visit(mut self, cursor: &Cursor) -> Transition {
+	/// Resume the literal after the closing brace of an interpolated expression.
+	pub fn resume(cursor: &Cursor) -> Self {
+		Self {
+			value: Vec::new(),
+			escaping: None,
+			interpolating: false,
+			resumed: true,
+			pos: cursor.pos(),
+		}
+	}
+
+
+	pub fn visit(mut self, cursor: &Cursor, interpolations: &mut usize) -> Transition {
 		match (&self, cursor.peek()) {
 			// EOF while scanning a literal is always an error.
 			(_, None) => Transition::error(Root, Error::unexpected_eof(cursor.pos())),
 
 			// Escaped character.
-			(&Self { escaping: Some((offset, pos)), .. }, Some(value)) => {
-				self.escaping = None;
-
-				if let Some(c) = validate_escape(value) {
-					self.value.push(c);
-					Transition::step(self)
-				} else {
-					let escape_sequence = &cursor.slice()[offset ..= cursor.offset()];
-					Transition::error(self, Error::invalid_escape_sequence(escape_sequence, pos))
+			(&Self { escaping: Some((offset, pos)), .. }, Some(_)) => {
+				let escape_sequence = &cursor.slice()[offset ..= cursor.offset()];
+
+				match Escape::visit(escape_sequence, validate_escape) {
+					Escape::Incomplete => Transition::step(self),
+
+					Escape::Invalid => {
+						self.escaping = None;
+						Transition::error(self, Error::invalid_escape_sequence(escape_sequence, pos))
+					}
+
+					// Don't consume the character, which may be the closing quote.
+					Escape::Unterminated => {
+						self.escaping = None;
+						let escape_sequence = &escape_sequence[.. escape_sequence.len() - 1];
+						Transition::resume_error(self, Error::invalid_escape_sequence(escape_sequence, pos))
+					}
+
+					escape => {
+						self.escaping = None;
+						escape.push_to(&mut self.value);
+						Transition::step(self)
+					}
 				}
 			}
 
+			// Open brace of an interpolated expression.
+			(&Self { interpolating: true, .. }, Some(b'{')) => {
+				*interpolations += 1;
+
+				let value = self.value.into_boxed_slice();
+				let kind =
+					if self.resumed {
+						TokenKind::InterpolationMiddle(value)
+					} else {
+						TokenKind::InterpolationStart(value)
+					};
+
+				Transition::produce(Root, Token { kind, pos: self.pos })
+			}
+
 			// Begin of escape sequence.
 			(_, Some(b'\\')) => {
 				self.escaping = Some((cursor.offset(), cursor.pos()));
@@ -131,13 +203,23 @@ impl StringLiteral {
 			}
 
 			// Closing quote.
-			(_, Some(b'\"')) => Transition::produce(
-				Root,
-				Token {
-					kind: TokenKind::Literal(Literal::String(self.value.into_boxed_slice())),
-					pos: self.pos,
-				},
-			),
+			(_, Some(b'\"')) => {
+				let value = self.value.into_boxed_slice();
+				let kind =
+					if self.resumed {
+						TokenKind::InterpolationEnd(value)
+					} else {
+						TokenKind::Literal(Literal::String(value))
+					};
+
+				Transition::produce(Root, Token { kind, pos: self.pos })
+			}
+
+			// Begin of interpolation.
+			(_, Some(b'$')) if cursor.slice().get(cursor.offset() + 1) == Some(&b'{') => {
+				self.interpolating = true;
+				Transition::step(self)
+			}
 
 			// Ordinary character.
 			(_, Some(value)) => {
@@ -163,8 +245,14 @@ fn validate_escape(sequence: u8) -> Option<u8> {
 		b'\'' => Some(b'\''),
 		b'n' => Some(b'\n'),
 		b't' => Some(b'\t'),
+		b'r' => Some(b'\r'),
+		b'e' => Some(b'\x1b'),
+		b'a' => Some(b'\x07'),
 		b'0' => Some(b'\0'),
 		b'\\' => Some(b'\\'),
+		b'$' => Some(b'$'),
+		b'{' => Some(b'{'),
+		b'}' => Some(b'}'),
 		_ => None,
 	}
 }
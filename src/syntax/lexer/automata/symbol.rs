@@ -3,6 +3,7 @@ use super::{
 	CommandOperator,
 	Cursor,
 	Error,
+	Heredoc,
 	Operator,
 	Root,
 	SourcePos,
@@ -13,17 +14,19 @@ use super::{
 };
 
 
-/// The state for lexing two-character symbols.
+/// The state for lexing multi-character symbols.
 #[derive(Debug)]
 pub(super) struct Symbol {
 	first: u8,
+	/// The second character, for symbols which may have three characters.
+	second: Option<u8>,
 	pos: SourcePos,
 }
 
 
 impl Symbol {
 	pub fn from_first(first: u8, cursor: &Cursor) -> Self {
-		Self { first, pos: cursor.pos() }
+		Self { first, second: None, pos: cursor.pos() }
 	}
 
 
@@ -34,30 +37,54 @@ impl Symbol {
 
 		let skip_produce = |output| Transition::resume_produce(Root, output);
 
-		match (self.first, cursor.peek()) {
-			(b'>', Some(b'=')) => Transition::produce(Root, operator(Operator::GreaterEquals)),
-			(b'>', _) => skip_produce(operator(Operator::Greater)),
+		match (self.first, self.second, cursor.peek()) {
+			(b'.', Some(b'.'), Some(b'.')) => Transition::produce(Root, token(TokenKind::Ellipsis)),
+			(b'.', Some(b'.'), _) => unexpected(self.first),
+			(b'.', _, Some(b'.')) => Transition::step(Self { second: Some(b'.'), ..self }),
+			(b'.', _, _) => skip_produce(operator(Operator::Dot)),
+
+			(b'>', _, Some(b'=')) => Transition::produce(Root, operator(Operator::GreaterEquals)),
+			(b'>', _, Some(b'>')) => Transition::produce(Root, operator(Operator::ShiftRight)),
+			(b'>', _, _) => skip_produce(operator(Operator::Greater)),
+
+			(b'<', _, Some(b'=')) => Transition::produce(Root, operator(Operator::LowerEquals)),
+			(b'<', _, Some(b'<')) => Transition::produce(Root, operator(Operator::ShiftLeft)),
+			(b'<', _, _) => skip_produce(operator(Operator::Lower)),
+
+			(b'+', Some(b'+'), Some(b'=')) => Transition::produce(Root, operator(Operator::ConcatAssign)),
+			(b'+', Some(b'+'), _) => skip_produce(operator(Operator::Concat)),
+			(b'+', _, Some(b'+')) => Transition::step(Self { second: Some(b'+'), ..self }),
+			(b'+', _, Some(b'=')) => Transition::produce(Root, operator(Operator::PlusAssign)),
+			(b'+', _, _) => skip_produce(operator(Operator::Plus)),
 
-			(b'<', Some(b'=')) => Transition::produce(Root, operator(Operator::LowerEquals)),
-			(b'<', _) => skip_produce(operator(Operator::Lower)),
+			(b'-', _, Some(b'=')) => Transition::produce(Root, operator(Operator::MinusAssign)),
+			(b'-', _, _) => skip_produce(operator(Operator::Minus)),
 
-			(b'+', Some(b'+')) => Transition::produce(Root, operator(Operator::Concat)),
-			(b'+', _) => skip_produce(operator(Operator::Plus)),
+			(b'*', _, Some(b'=')) => Transition::produce(Root, operator(Operator::TimesAssign)),
+			(b'*', _, Some(b'*')) => Transition::produce(Root, operator(Operator::Pow)),
+			(b'*', _, _) => skip_produce(operator(Operator::Times)),
 
-			(b'=', Some(b'=')) => Transition::produce(Root, operator(Operator::Equals)),
-			(b'=', _) => skip_produce(operator(Operator::Assign)),
+			(b'/', _, Some(b'=')) => Transition::produce(Root, operator(Operator::DivAssign)),
+			(b'/', _, Some(b'/')) => Transition::produce(Root, operator(Operator::IntDiv)),
+			(b'/', _, _) => skip_produce(operator(Operator::Div)),
 
-			(b'!', Some(b'=')) => Transition::produce(Root, operator(Operator::NotEquals)),
-			(b'!', _) => unexpected(self.first),
+			(b'=', _, Some(b'=')) => Transition::produce(Root, operator(Operator::Equals)),
+			(b'=', _, _) => skip_produce(operator(Operator::Assign)),
 
-			(b'@', Some(b'[')) => Transition::produce(Root, token(TokenKind::OpenDict)),
-			(b'@', _) => unexpected(self.first),
+			(b'!', _, Some(b'=')) => Transition::produce(Root, operator(Operator::NotEquals)),
+			(b'!', _, _) => unexpected(self.first),
 
-			(b'$', Some(b'{')) => Transition::produce(Command, token(TokenKind::CaptureCommand)),
-			(b'$', _) => unexpected(self.first),
+			(b'@', _, Some(b'[')) => Transition::produce(Root, token(TokenKind::OpenDict)),
+			(b'@', _, _) => unexpected(self.first),
 
-			(b'&', Some(b'{')) => Transition::produce(Command, token(TokenKind::AsyncCommand)),
-			(b'&', _) => unexpected(self.first),
+			(b'$', _, Some(b'{')) => Transition::produce(Command, token(TokenKind::CaptureCommand)),
+			(b'$', _, _) => unexpected(self.first),
+
+			(b'&', _, Some(b'{')) => Transition::produce(Command, token(TokenKind::AsyncCommand)),
+			(b'&', _, _) => skip_produce(operator(Operator::BitAnd)),
+
+			(b'|', _, Some(b'{')) => Transition::produce(Command, token(TokenKind::StreamCommand)),
+			(b'|', _, _) => skip_produce(operator(Operator::BitOr)),
 
 			// We must have covered all possibilites for the first character. The peeked
 			// character is wildcarded, which will cover everthing including EOF (None).
@@ -99,12 +126,23 @@ impl CommandSymbol {
 			(b'>', Some(b'>')) => produce(operator(CommandOperator::Output {
 				append: true,
 			})),
+			(b'>', Some(b'&')) => produce(operator(CommandOperator::Duplicate {
+				input: false,
+			})),
 			(b'>', _) => skip_produce(operator(CommandOperator::Output {
 				append: false,
 			})),
 
-			(b'<', Some(b'<')) => produce(operator(CommandOperator::Input {
-				literal: true,
+			(b'<', Some(b'<')) => {
+				let operator = operator(CommandOperator::Input { literal: true });
+
+				match Heredoc::header(cursor, self.pos) {
+					Some(heredoc) => Transition::produce(heredoc, operator),
+					None => produce(operator),
+				}
+			}
+			(b'<', Some(b'&')) => produce(operator(CommandOperator::Duplicate {
+				input: true,
 			})),
 			(b'<', _) => skip_produce(operator(CommandOperator::Input {
 				literal: false,
@@ -144,12 +182,9 @@ impl SymbolChar {
 
 		match first {
 			// Single character.
-			b'-' => operator(Operator::Minus),
-			b'*' => operator(Operator::Times),
-			b'/' => operator(Operator::Div),
 			b'%' => operator(Operator::Mod),
-			b'.' => operator(Operator::Dot),
 			b'?' => operator(Operator::Try),
+			b'^' => operator(Operator::BitXor),
 			b':' => token(TokenKind::Colon),
 			b',' => token(TokenKind::Comma),
 			b'(' => token(TokenKind::OpenParens),
@@ -159,14 +194,19 @@ impl SymbolChar {
 			b'{' => token(TokenKind::Command),
 
 			// Double character.
+			b'.' => double(first),
 			b'>' => double(first),
 			b'<' => double(first),
 			b'+' => double(first),
+			b'-' => double(first),
+			b'*' => double(first),
+			b'/' => double(first),
 			b'=' => double(first),
 			b'!' => double(first),
 			b'@' => double(first),
 			b'$' => double(first),
 			b'&' => double(first),
+			b'|' => double(first),
 
 			// Not a symbol character:
 			_ => SymbolChar::None,
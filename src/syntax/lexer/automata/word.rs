@@ -18,12 +18,21 @@ use super::{
 pub(super) struct Word {
 	start_offset: usize,
 	pos: SourcePos,
+	/// Whether the word follows the dot operator. Such words are field names, and
+	/// therefore are always identifiers, even if they match a keyword.
+	field: bool,
 }
 
 
 impl Word {
 	pub fn at(cursor: &Cursor) -> Self {
-		Self { start_offset: cursor.offset(), pos: cursor.pos() }
+		let field = cursor.slice()[.. cursor.offset()]
+			.iter()
+			.rev()
+			.find(|&&c| c != b' ' && c != b'\t')
+			== Some(&b'.');
+
+		Self { start_offset: cursor.offset(), pos: cursor.pos(), field }
 	}
 
 
@@ -37,7 +46,12 @@ impl Word {
 			// If we visit EOF or a non-identifier character, we should just produce.
 			_ => {
 				let word = &cursor.slice()[self.start_offset .. cursor.offset()];
-				let token = to_token(word, interner);
+				let token =
+					if self.field {
+						TokenKind::Identifier(interner.get_or_intern(word))
+					} else {
+						to_token(word, interner)
+					};
 
 				Transition::resume_produce(Root, Token { kind: token, pos: self.pos })
 			}
@@ -69,6 +83,10 @@ pub fn to_token(word: &[u8], interner: &mut SymbolInterner) -> TokenKind {
 		b"function" => TokenKind::Keyword(Keyword::Function),
 		b"return" => TokenKind::Keyword(Keyword::Return),
 		b"break" => TokenKind::Keyword(Keyword::Break),
+		b"continue" => TokenKind::Keyword(Keyword::Continue),
+		b"match" => TokenKind::Keyword(Keyword::Match),
+		b"with" => TokenKind::Keyword(Keyword::With),
+		b"case" => TokenKind::Keyword(Keyword::Case),
 		b"self" => TokenKind::Keyword(Keyword::Self_),
 
 		// Literals:
@@ -74,7 +74,7 @@ impl<'a> From<&'a Source> for Cursor<'a> {
 		Self {
 			input: &source.contents,
 			offset: 0,
-			pos: SourcePos { line: 1, column: 0, path: source.path }
+			pos: SourcePos { line: 1, column: 0, path: source.path, len: 0 }
 		}
 	}
 }
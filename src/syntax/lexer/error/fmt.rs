@@ -26,6 +26,14 @@ impl std::fmt::Display for ErrorKind {
 				write!(f, "invalid number '{}'", String::from_utf8_lossy(number))?;
 			}
 
+			Self::IntegerOutOfRange(number) => {
+				write!(f, "integer literal out of range '{}'", String::from_utf8_lossy(number))?;
+			}
+
+			Self::InvalidDigit { digit, radix } => {
+				write!(f, "invalid digit '{}' for base {} literal", (*digit as char).escape_debug(), radix)?;
+			}
+
 			Self::InvalidIdentifier(ident) => {
 				write!(f, "invalid identifier '{}'", String::from_utf8_lossy(ident))?;
 			}
@@ -16,6 +16,10 @@ pub enum ErrorKind {
 	InvalidEscapeSequence(Box<[u8]>),
 	/// Invalid number literal, both integer and floating point.
 	InvalidNumber(Box<[u8]>),
+	/// Integer literal which does not fit in 64 bits.
+	IntegerOutOfRange(Box<[u8]>),
+	/// Invalid digit for the radix of a prefixed integer literal.
+	InvalidDigit { digit: u8, radix: u32 },
 	/// Invalid identifier, only possible in dollar braces (${}).
 	InvalidIdentifier(Box<[u8]>),
 }
@@ -59,6 +63,13 @@ impl Error {
 		}
 	}
 
+	pub fn integer_out_of_range(number: &[u8], pos: SourcePos) -> Self {
+		Self {
+			error: ErrorKind::IntegerOutOfRange(number.into()),
+			pos,
+		}
+	}
+
 	pub fn invalid_identifier(ident: &[u8], pos: SourcePos) -> Self {
 		Self {
 			error: ErrorKind::InvalidIdentifier(ident.into()),
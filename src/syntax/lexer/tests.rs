@@ -94,7 +94,7 @@ fn test_simple_function() {
 #[test]
 fn test_invalid_tokens() {
 	let input = r#"
-		function foo(bar, baz) |
+		function foo(bar, baz) ~
 			if bar or baz == nil then # here's a comment
 				let $result = do_something()
 				return @}result
@@ -120,7 +120,7 @@ fn test_invalid_tokens() {
 			token!(TokenKind::Comma),
 			token!(TokenKind::Identifier(baz1)),
 			token!(TokenKind::CloseParens),
-			error!(ErrorKind::Unexpected(b'|')),
+			error!(ErrorKind::Unexpected(b'~')),
 			token!(TokenKind::Keyword(Keyword::If)),
 			token!(TokenKind::Identifier(bar2)),
 			token!(TokenKind::Operator(Operator::Or)),
@@ -255,6 +255,98 @@ fn test_string_literals() {
 }
 
 
+#[test]
+fn test_string_interpolation() {
+	let input = r#"
+		"a ${ x } b ${ "c ${y}" }\${z}"
+	"#;
+
+	let mut interner = symbol::Interner::new();
+	let path = interner.get_or_intern("<test>");
+	let source = Source { path, contents: input.as_bytes().into() };
+	let cursor = Cursor::from(&source);
+	let lexer = Lexer::new(cursor, &mut interner);
+
+	let tokens: Vec<Result<Token, Error>> = lexer.collect();
+
+	assert_matches!(
+		&tokens[..],
+		[
+			token!(TokenKind::InterpolationStart(s1)),
+			token!(TokenKind::Identifier(x)),
+			token!(TokenKind::InterpolationMiddle(s2)),
+			token!(TokenKind::InterpolationStart(s3)),
+			token!(TokenKind::Identifier(y)),
+			token!(TokenKind::InterpolationEnd(s4)),
+			token!(TokenKind::InterpolationEnd(s5)),
+		]
+			=> {
+				assert_symbol!(interner, x, "x");
+				assert_symbol!(interner, y, "y");
+				assert_eq!(s1.as_ref(), b"a ");
+				assert_eq!(s2.as_ref(), b" b ");
+				assert_eq!(s3.as_ref(), b"c ");
+				assert!(s4.is_empty());
+				assert_eq!(s5.as_ref(), b"${z}");
+			}
+	);
+}
+
+
+#[test]
+fn test_escape_sequences() {
+	let input = r#"
+		"\x41\u{e9}\u{1F600}\r\e\a" '\x7f' '\u{41}' '\e'
+		"\xZZ" "\u{110000}" '\u{e9}'
+		"\x4" '\x4' "\u{4"
+	"#;
+
+	let mut interner = symbol::Interner::new();
+	let path = interner.get_or_intern("<test>");
+	let source = Source { path, contents: input.as_bytes().into() };
+	let cursor = Cursor::from(&source);
+	let lexer = Lexer::new(cursor, &mut interner);
+
+	let tokens: Vec<Result<Token, Error>> = lexer.collect();
+
+	assert_matches!(
+		&tokens[..],
+		[
+			token!(TokenKind::Literal(Literal::String(lit1))),
+			token!(TokenKind::Literal(Literal::Byte(0x7f))),
+			token!(TokenKind::Literal(Literal::Byte(b'A'))),
+			token!(TokenKind::Literal(Literal::Byte(0x1b))),
+
+			error!(ErrorKind::InvalidEscapeSequence(e1)),
+			token!(TokenKind::Literal(Literal::String(lit2))),
+			error!(ErrorKind::InvalidEscapeSequence(e2)),
+			token!(TokenKind::Literal(Literal::String(_))),
+			error!(ErrorKind::InvalidEscapeSequence(e3)),
+			token!(TokenKind::Literal(Literal::Byte(_))),
+
+			// Invalid escapes don't consume the closing quote.
+			error!(ErrorKind::InvalidEscapeSequence(e4)),
+			token!(TokenKind::Literal(Literal::String(lit3))),
+			error!(ErrorKind::InvalidEscapeSequence(e5)),
+			token!(TokenKind::Literal(Literal::Byte(_))),
+			error!(ErrorKind::InvalidEscapeSequence(e6)),
+			token!(TokenKind::Literal(Literal::String(_))),
+		]
+			=> {
+				assert_eq!(lit1.as_ref(), "A\u{e9}\u{1F600}\r\x1b\x07".as_bytes());
+				assert_eq!(e1.as_ref(), b"\\x");
+				assert_eq!(lit2.as_ref(), b"ZZ");
+				assert_eq!(e2.as_ref(), b"\\u{110000}");
+				assert_eq!(e3.as_ref(), b"\\u{e9}");
+				assert_eq!(e4.as_ref(), b"\\x4");
+				assert_eq!(lit3.as_ref(), b"");
+				assert_eq!(e5.as_ref(), b"\\x4");
+				assert_eq!(e6.as_ref(), b"\\u{4");
+			}
+	);
+}
+
+
 #[test]
 fn test_number_literals() {
 	let input = r#"
@@ -294,6 +386,87 @@ fn test_number_literals() {
 }
 
 
+#[test]
+fn test_prefixed_number_literals() {
+	let input = r#"
+		0x1F + 0o755 + 0b1010 + 1_000_000 + 0xdead_beef + 1_0.2_5
+	"#;
+
+	let mut interner = symbol::Interner::new();
+	let path = interner.get_or_intern("<test>");
+	let source = Source { path, contents: input.as_bytes().into() };
+	let cursor = Cursor::from(&source);
+	let lexer = Lexer::new(cursor, &mut interner);
+
+	let tokens: Vec<Result<Token, Error>> = lexer.collect();
+
+	assert_matches!(
+		&tokens[..],
+		[
+			token!(TokenKind::Literal(Literal::Int(i1))),
+			token!(TokenKind::Operator(Operator::Plus)),
+			token!(TokenKind::Literal(Literal::Int(i2))),
+			token!(TokenKind::Operator(Operator::Plus)),
+			token!(TokenKind::Literal(Literal::Int(i3))),
+			token!(TokenKind::Operator(Operator::Plus)),
+			token!(TokenKind::Literal(Literal::Int(i4))),
+			token!(TokenKind::Operator(Operator::Plus)),
+			token!(TokenKind::Literal(Literal::Int(i5))),
+			token!(TokenKind::Operator(Operator::Plus)),
+			token!(TokenKind::Literal(Literal::Float(f1))),
+		]
+			=> {
+				assert_eq!(*i1, 0x1F);
+				assert_eq!(*i2, 0o755);
+				assert_eq!(*i3, 0b1010);
+				assert_eq!(*i4, 1_000_000);
+				assert_eq!(*i5, 0xdead_beef);
+				assert_eq!(*f1, 10.25);
+			}
+	);
+}
+
+
+#[test]
+fn test_invalid_number_literals() {
+	let input = r#"
+		0b102 0o8 0x 0x_1 1__0 1_ 1_abc 0x8000000000000000 9223372036854775808
+	"#;
+
+	let mut interner = symbol::Interner::new();
+	let path = interner.get_or_intern("<test>");
+	let source = Source { path, contents: input.as_bytes().into() };
+	let cursor = Cursor::from(&source);
+	let lexer = Lexer::new(cursor, &mut interner);
+
+	let tokens: Vec<Result<Token, Error>> = lexer.collect();
+
+	assert_matches!(
+		&tokens[..],
+		[
+			error!(ErrorKind::InvalidDigit { digit: b'2', radix: 2 }),
+			error!(ErrorKind::InvalidDigit { digit: b'8', radix: 8 }),
+			error!(ErrorKind::InvalidNumber(n1)),
+			error!(ErrorKind::InvalidNumber(n2)),
+			error!(ErrorKind::InvalidNumber(n3)),
+			error!(ErrorKind::InvalidNumber(n4)),
+			error!(ErrorKind::InvalidNumber(n5)),
+			error!(ErrorKind::IntegerOutOfRange(n6)),
+			error!(ErrorKind::IntegerOutOfRange(n7)),
+		]
+			=> {
+				assert_eq!(n1.as_ref(), b"0x");
+				assert_eq!(n2.as_ref(), b"0x_1");
+				assert_eq!(n3.as_ref(), b"1__0");
+				assert_eq!(n4.as_ref(), b"1_");
+				assert_eq!(n5.as_ref(), b"1_abc");
+				assert_eq!(n6.as_ref(), b"0x8000000000000000");
+				assert_eq!(n7.as_ref(), b"9223372036854775808");
+			}
+	);
+}
+
+
 #[test]
 fn test_command_block() {
 	let input = r#"
@@ -386,6 +559,187 @@ fn test_command_block() {
 }
 
 
+#[test]
+fn test_heredoc() {
+	let input = "{\n\tcat <<EOF\nhello $name\n  \\$5\nEOF\n\tcat <<-'RAW'\n\t\t$raw\n\t\t  text\n\tRAW\n}";
+
+	let mut interner = symbol::Interner::new();
+	let path = interner.get_or_intern("<test>");
+	let source = Source { path, contents: input.as_bytes().into() };
+	let cursor = Cursor::from(&source);
+	let lexer = Lexer::new(cursor, &mut interner);
+
+	let tokens: Vec<Result<Token, Error>> = lexer.collect();
+
+	let literal = |lit: &str| ArgUnit::Literal(lit.as_bytes().into());
+	let sym = |ident: &str| interner.get(ident).expect("symbol not found");
+
+	assert_matches!(
+		&tokens[..],
+		[
+			token!(TokenKind::Command),
+			token!(TokenKind::Argument(_)),
+			token!(TokenKind::CmdOperator(CommandOperator::Input { literal: true })),
+			token!(TokenKind::Argument(heredoc1)),
+			token!(TokenKind::Argument(_)),
+			token!(TokenKind::CmdOperator(CommandOperator::Input { literal: true })),
+			token!(TokenKind::Argument(heredoc2)),
+			token!(TokenKind::CloseCommand),
+		]
+			=> {
+				assert_matches!(
+					heredoc1.as_ref(),
+					&[ArgPart::DoubleQuoted(ref units)]
+						if matches!(
+							units.as_ref(),
+							&[ref hello, ArgUnit::Dollar { ref symbol, .. }, ref dollar]
+								if *hello == literal("hello ")
+									&& *symbol == sym("name")
+									&& *dollar == literal("\n  $5")
+						)
+				);
+				assert_eq!(heredoc2.as_ref(), &[ArgPart::DoubleQuoted(vec![literal("$raw\n  text")].into())]);
+			}
+	);
+}
+
+
+#[test]
+fn test_heredoc_header_continuation() {
+	let input = "{\n\tcat <<EOF | grep x > out;\nbody\n  EOF\nEOF\n\techo after\n}";
+
+	let mut interner = symbol::Interner::new();
+	let path = interner.get_or_intern("<test>");
+	let source = Source { path, contents: input.as_bytes().into() };
+	let cursor = Cursor::from(&source);
+	let lexer = Lexer::new(cursor, &mut interner);
+
+	let tokens: Vec<Result<Token, Error>> = lexer.collect();
+
+	let literal = |lit: &str| ArgUnit::Literal(lit.as_bytes().into());
+
+	assert_matches!(
+		&tokens[..],
+		[
+			token!(TokenKind::Command),
+			token!(TokenKind::Argument(_)),
+			token!(TokenKind::CmdOperator(CommandOperator::Input { literal: true })),
+			token!(TokenKind::Argument(heredoc)),
+			token!(TokenKind::Pipe),
+			token!(TokenKind::Argument(_)),
+			token!(TokenKind::Argument(_)),
+			token!(TokenKind::CmdOperator(CommandOperator::Output { append: false })),
+			token!(TokenKind::Argument(_)),
+			token!(TokenKind::Semicolon),
+			Ok(Token { kind: TokenKind::Argument(_), pos: SourcePos { line: 6, column: 1, .. } }),
+			token!(TokenKind::Argument(_)),
+			token!(TokenKind::CloseCommand),
+		]
+			=> {
+				// Without a dash, indented delimiters don't terminate the heredoc.
+				assert_eq!(heredoc.as_ref(), &[ArgPart::DoubleQuoted(vec![literal("body\n  EOF")].into())]);
+			}
+	);
+}
+
+
+#[test]
+fn test_match_keywords() {
+	let input = "match rex.match(x) with case _ then end";
+
+	let mut interner = symbol::Interner::new();
+	let path = interner.get_or_intern("<test>");
+	let source = Source { path, contents: input.as_bytes().into() };
+	let cursor = Cursor::from(&source);
+	let lexer = Lexer::new(cursor, &mut interner);
+
+	let tokens: Vec<Result<Token, Error>> = lexer.collect();
+
+	assert_matches!(
+		&tokens[..],
+		[
+			token!(TokenKind::Keyword(Keyword::Match)),
+			token!(TokenKind::Identifier(_)),
+			token!(TokenKind::Operator(Operator::Dot)),
+			token!(TokenKind::Identifier(field)),
+			token!(TokenKind::OpenParens),
+			token!(TokenKind::Identifier(_)),
+			token!(TokenKind::CloseParens),
+			token!(TokenKind::Keyword(Keyword::With)),
+			token!(TokenKind::Keyword(Keyword::Case)),
+			token!(TokenKind::Identifier(_)),
+			token!(TokenKind::Keyword(Keyword::Then)),
+			token!(TokenKind::Keyword(Keyword::End)),
+		]
+			=> assert_symbol!(interner, field, "match")
+	);
+}
+
+
+#[test]
+fn test_ellipsis() {
+	let input = "function (a, ...b) a.b end ..c";
+
+	let mut interner = symbol::Interner::new();
+	let path = interner.get_or_intern("<test>");
+	let source = Source { path, contents: input.as_bytes().into() };
+	let cursor = Cursor::from(&source);
+	let lexer = Lexer::new(cursor, &mut interner);
+
+	let tokens: Vec<Result<Token, Error>> = lexer.collect();
+
+	assert_matches!(
+		&tokens[..],
+		[
+			token!(TokenKind::Keyword(Keyword::Function)),
+			token!(TokenKind::OpenParens),
+			token!(TokenKind::Identifier(_)),
+			token!(TokenKind::Comma),
+			token!(TokenKind::Ellipsis),
+			token!(TokenKind::Identifier(_)),
+			token!(TokenKind::CloseParens),
+			token!(TokenKind::Identifier(_)),
+			token!(TokenKind::Operator(Operator::Dot)),
+			token!(TokenKind::Identifier(_)),
+			token!(TokenKind::Keyword(Keyword::End)),
+			error!(ErrorKind::Unexpected(b'.')),
+			token!(TokenKind::Identifier(_)),
+		]
+	);
+}
+
+
+
+#[test]
+fn test_stream_command() {
+	let input = "for line in |{ ls } do end a | b";
+
+	let mut interner = symbol::Interner::new();
+	let path = interner.get_or_intern("<test>");
+	let source = Source { path, contents: input.as_bytes().into() };
+	let cursor = Cursor::from(&source);
+	let lexer = Lexer::new(cursor, &mut interner);
+
+	let tokens: Vec<Result<Token, Error>> = lexer.collect();
+
+	assert_matches!(
+		&tokens[..],
+		[
+			token!(TokenKind::Keyword(Keyword::For)),
+			token!(TokenKind::Identifier(_)),
+			token!(TokenKind::Keyword(Keyword::In)),
+			token!(TokenKind::StreamCommand),
+			token!(TokenKind::Argument(_)),
+			token!(TokenKind::CloseCommand),
+			token!(TokenKind::Keyword(Keyword::Do)),
+			token!(TokenKind::Keyword(Keyword::End)),
+			token!(TokenKind::Identifier(_)),
+			token!(TokenKind::Operator(Operator::BitOr)),
+			token!(TokenKind::Identifier(_)),
+		]
+	);
+}
+
 #[test]
 fn test_expansions() {
 	let input = r#"
@@ -37,6 +37,10 @@ impl std::fmt::Display for Keyword {
 					Self::Function => "function",
 					Self::Return => "return",
 					Self::Break => "break",
+					Self::Continue => "continue",
+					Self::Match => "match",
+					Self::With => "with",
+					Self::Case => "case",
 					Self::Self_ => "self",
 				}
 			)
@@ -71,7 +75,14 @@ impl std::fmt::Display for Operator {
 			Self::Minus => color::Fg(color::Yellow, "-").fmt(f),
 			Self::Times => color::Fg(color::Yellow, "*").fmt(f),
 			Self::Div => color::Fg(color::Yellow, "/").fmt(f),
+			Self::IntDiv => color::Fg(color::Yellow, "//").fmt(f),
 			Self::Mod => color::Fg(color::Yellow, "%").fmt(f),
+			Self::Pow => color::Fg(color::Yellow, "**").fmt(f),
+			Self::BitAnd => color::Fg(color::Yellow, "&").fmt(f),
+			Self::BitOr => color::Fg(color::Yellow, "|").fmt(f),
+			Self::BitXor => color::Fg(color::Yellow, "^").fmt(f),
+			Self::ShiftLeft => color::Fg(color::Yellow, "<<").fmt(f),
+			Self::ShiftRight => color::Fg(color::Yellow, ">>").fmt(f),
 			Self::Equals => color::Fg(color::Yellow, "==").fmt(f),
 			Self::NotEquals => color::Fg(color::Yellow, "!=").fmt(f),
 			Self::Greater => color::Fg(color::Yellow, ">").fmt(f),
@@ -84,6 +95,11 @@ impl std::fmt::Display for Operator {
 			Self::Concat => color::Fg(color::Yellow, "++").fmt(f),
 			Self::Dot => color::Fg(color::Yellow, ".").fmt(f),
 			Self::Assign => "=".fmt(f),
+			Self::PlusAssign => color::Fg(color::Yellow, "+=").fmt(f),
+			Self::MinusAssign => color::Fg(color::Yellow, "-=").fmt(f),
+			Self::TimesAssign => color::Fg(color::Yellow, "*=").fmt(f),
+			Self::DivAssign => color::Fg(color::Yellow, "/=").fmt(f),
+			Self::ConcatAssign => color::Fg(color::Yellow, "++=").fmt(f),
 			Self::Try => color::Fg(color::Yellow, "?").fmt(f),
 		}
 	}
@@ -184,6 +200,8 @@ impl std::fmt::Display for CommandOperator {
 					Self::Output { append: false } => ">",
 					Self::Input { literal: true } => "<<",
 					Self::Input { literal: false } => "<",
+					Self::Duplicate { input: false } => ">&",
+					Self::Duplicate { input: true } => "<&",
 					Self::Try => "?",
 				}
 			)
@@ -203,14 +221,31 @@ impl<'a> Display<'a> for TokenKind {
 			Self::Literal(lit) => lit.fmt(f),
 			Self::Colon => ":".fmt(f),
 			Self::Comma => ",".fmt(f),
+			Self::Ellipsis => "...".fmt(f),
 			Self::OpenParens => "(".fmt(f),
 			Self::CloseParens => ")".fmt(f),
 			Self::OpenBracket => "[".fmt(f),
 			Self::OpenDict => "@[".fmt(f),
 			Self::CloseBracket => "]".fmt(f),
+			Self::InterpolationStart(s) => write!(
+				f,
+				"\"{}${{",
+				color::Bold(String::from_utf8_lossy(s).escape_debug())
+			),
+			Self::InterpolationMiddle(s) => write!(
+				f,
+				"}}{}${{",
+				color::Bold(String::from_utf8_lossy(s).escape_debug())
+			),
+			Self::InterpolationEnd(s) => write!(
+				f,
+				"}}{}\"",
+				color::Bold(String::from_utf8_lossy(s).escape_debug())
+			),
 			Self::Command => "{".fmt(f),
 			Self::CaptureCommand => "${".fmt(f),
 			Self::AsyncCommand => "&{".fmt(f),
+			Self::StreamCommand => "|{".fmt(f),
 			Self::CloseCommand => "}".fmt(f),
 			Self::Argument(parts) => {
 				for part in parts.iter() {
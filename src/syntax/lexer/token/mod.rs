@@ -20,6 +20,10 @@ pub enum Keyword {
 	Function,
 	Return,
 	Break,
+	Continue,
+	Match,
+	With,
+	Case,
 	Self_,
 }
 
@@ -41,11 +45,19 @@ pub enum Literal {
 /// Non-command operators.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum Operator {
-	Plus,  // +
-	Minus, // -
-	Times, // *
-	Div,   // /
-	Mod,   // %
+	Plus,   // +
+	Minus,  // -
+	Times,  // *
+	Div,    // /
+	IntDiv, // //
+	Mod,    // %
+	Pow,    // **
+
+	BitAnd,     // &
+	BitOr,      // |
+	BitXor,     // ^
+	ShiftLeft,  // <<
+	ShiftRight, // >>
 
 	Equals,        // ==
 	NotEquals,     // !=
@@ -61,7 +73,12 @@ pub enum Operator {
 	Concat, // ++
 	Dot,    // .
 
-	Assign, // =
+	Assign,       // =
+	PlusAssign,   // +=
+	MinusAssign,  // -=
+	TimesAssign,  // *=
+	DivAssign,    // /=
+	ConcatAssign, // ++=
 
 	Try, // ?
 }
@@ -89,9 +106,29 @@ impl Operator {
 	}
 
 
-	/// Multiplicative arithmetic operators (*, /, %).
+	/// Multiplicative arithmetic operators (*, /, //, %).
 	pub fn is_factor(&self) -> bool {
-		matches!(self, Self::Times | Self::Div | Self::Mod)
+		matches!(self, Self::Times | Self::Div | Self::IntDiv | Self::Mod)
+	}
+
+
+	/// Bit shift operators (<<, >>).
+	pub fn is_shift(&self) -> bool {
+		matches!(self, Self::ShiftLeft | Self::ShiftRight)
+	}
+
+
+	/// Compound assignment operators (+=, -=, *=, /=, ++=), which combine a binary
+	/// operator with assignment. Produces the binary operator if so.
+	pub fn compound_assign(&self) -> Option<Self> {
+		match self {
+			Self::PlusAssign => Some(Self::Plus),
+			Self::MinusAssign => Some(Self::Minus),
+			Self::TimesAssign => Some(Self::Times),
+			Self::DivAssign => Some(Self::Div),
+			Self::ConcatAssign => Some(Self::Concat),
+			_ => None,
+		}
 	}
 
 
@@ -160,9 +197,10 @@ impl ArgPart {
 /// Operators in command blocks.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum CommandOperator {
-	Output { append: bool }, // >, >>
-	Input { literal: bool }, // <, <<
-	Try,                     // ?
+	Output { append: bool },    // >, >>
+	Input { literal: bool },    // <, <<
+	Duplicate { input: bool },  // >&, <&
+	Try,                        // ?
 }
 
 
@@ -171,7 +209,7 @@ impl CommandOperator {
 	pub fn is_redirection(&self) -> bool {
 		matches!(
 			self,
-			Self::Output { .. } | Self::Input { .. }
+			Self::Output { .. } | Self::Input { .. } | Self::Duplicate { .. }
 		)
 	}
 }
@@ -185,8 +223,9 @@ pub enum TokenKind {
 	Operator(Operator),
 	Literal(Literal),
 
-	Colon, // :
-	Comma, // ,
+	Colon,    // :
+	Comma,    // ,
+	Ellipsis, // ...
 
 	OpenParens,  // (
 	CloseParens, // )
@@ -195,10 +234,16 @@ pub enum TokenKind {
 	OpenDict,     // @[
 	CloseBracket, // ]
 
+	// Interpolated string literals are split around the interpolated expressions.
+	InterpolationStart(Box<[u8]>),  // "...${
+	InterpolationMiddle(Box<[u8]>), // }...${
+	InterpolationEnd(Box<[u8]>),    // }..."
+
 	// Command block tokens
 	Command,        // {
 	AsyncCommand,   // &{
 	CaptureCommand, // ${
+	StreamCommand,  // |{
 	CloseCommand,   // }
 
 	// A single argument may be composed of many parts.
@@ -217,7 +262,10 @@ impl TokenKind {
 	pub fn is_block_terminator(&self) -> bool {
 		matches!(
 			self,
-			TokenKind::Keyword(Keyword::End) | TokenKind::Keyword(Keyword::Else) | TokenKind::Keyword(Keyword::ElseIf)
+			TokenKind::Keyword(Keyword::End)
+				| TokenKind::Keyword(Keyword::Else)
+				| TokenKind::Keyword(Keyword::ElseIf)
+				| TokenKind::Keyword(Keyword::Case)
 		)
 	}
 
@@ -226,7 +274,10 @@ impl TokenKind {
 	pub fn is_command_block_starter(&self) -> bool {
 		matches!(
 			self,
-			TokenKind::Command | TokenKind::AsyncCommand | TokenKind::CaptureCommand
+			TokenKind::Command
+				| TokenKind::AsyncCommand
+				| TokenKind::CaptureCommand
+				| TokenKind::StreamCommand
 		)
 	}
 
@@ -14,7 +14,7 @@ pub use ast::Ast;
 pub use error::{Error, Errors};
 use lexer::Lexer;
 use parser::Parser;
-pub use source::{Source, SourcePos};
+pub use source::{Snippet, Source, SourceMap, SourcePos};
 pub use fmt::AnalysisDisplayContext;
 
 
@@ -93,20 +93,7 @@ where
 
 		let mut arguments = Vec::new();
 		loop {
-			let is_redirection = matches!(
-				&self.token,
-				// The current token is a single unquoted number
-				Some(Token { kind: TokenKind::Argument(parts), .. })
-					if matches!(parts.as_ref(), &[ref part] if part.is_unquoted_number())
-					// And the next token is a redirection operator.
-					&& matches!(
-						self.cursor.peek(),
-						Some(Token { kind: TokenKind::CmdOperator(op), .. })
-							if op.is_redirection()
-					)
-			);
-
-			if is_redirection {
+			if self.is_file_descriptor_prefix() {
 				break;
 			}
 
@@ -203,8 +190,14 @@ where
 	}
 
 
-	/// Parse a single redirection operation.
+	/// Parse a single redirection operation, with an optional prefixed file descriptor.
 	fn parse_redirection(&mut self) -> sync::Result<ast::Redirection, Error> {
+		let fd = if self.is_file_descriptor_prefix() {
+			self.parse_file_descriptor()
+		} else {
+			None
+		};
+
 		match &self.token {
 			// Input redirection.
 			&Some(Token { kind: TokenKind::CmdOperator(Operator::Input { literal }), .. }) => {
@@ -214,17 +207,34 @@ where
 					.with_sync(sync::Strategy::keep())?;
 
 				Ok(
-					ast::Redirection::Input { literal, source }
+					ast::Redirection::Input {
+						target: fd.unwrap_or_else(io::stdin_fd),
+						literal,
+						source,
+					}
 				)
 			}
 
-			// Expect a output redirection, with an optional prefixed file descriptor.
+			// Duplication or closing of a file descriptor.
+			&Some(Token { kind: TokenKind::CmdOperator(Operator::Duplicate { input }), .. }) => {
+				self.step();
+
+				let source = fd.unwrap_or_else(
+					if input { io::stdin_fd } else { io::stdout_fd }
+				);
+
+				let target = self.parse_duplicate_target()?;
+
+				Ok(
+					ast::Redirection::Output { source, target }
+				)
+			}
+
+			// Expect a output redirection.
 			Some(_) => {
-				let source_fd = self
-					.parse_file_descriptor()
-					.unwrap_or_else(io::stdout_fd);
+				let source = fd.unwrap_or_else(io::stdout_fd);
 
-				let redirection = self.parse_output_redirection(source_fd)?;
+				let redirection = self.parse_output_redirection(source)?;
 
 				Ok(redirection)
 			}
@@ -262,7 +272,7 @@ where
 				)
 			}
 
-			Some(token) => Err(Error::unexpected_msg(token.clone(), "output redirection"))
+			Some(token) => Err(Error::unexpected_msg(token.clone(), "redirection"))
 				.with_sync(sync::Strategy::skip_one()),
 
 			None => Err(Error::unexpected_eof())
@@ -271,6 +281,56 @@ where
 	}
 
 
+	/// Parse the target of a duplication operator, which may be a file descriptor or a
+	/// dash, which closes the source file descriptor.
+	fn parse_duplicate_target(&mut self) -> sync::Result<ast::RedirectionTarget, Error> {
+		if let Some(fd) = self.parse_file_descriptor() { // >& fd
+			return Ok(ast::RedirectionTarget::Fd(fd));
+		}
+
+		match &self.token {
+			Some(Token { kind: TokenKind::Argument(parts), .. })
+				if matches!(parts.as_ref(), [ArgPart::Unquoted(ArgUnit::Literal(lit))] if lit.as_ref() == b"-") => {
+				self.step();
+				Ok(ast::RedirectionTarget::Close) // >& -
+			}
+
+			Some(token) => Err(Error::unexpected_msg(token.clone(), "file descriptor or '-'"))
+				.with_sync(sync::Strategy::keep()),
+
+			None => Err(Error::unexpected_eof())
+				.with_sync(sync::Strategy::eof()),
+		}
+	}
+
+
+	/// Whether the current token is a file descriptor prefixing a redirection operator.
+	/// The number must be immediately followed by the operator, as in `2>`, otherwise it
+	/// is a regular argument, as in `head -n 5 < file`.
+	fn is_file_descriptor_prefix(&mut self) -> bool {
+		let (number, pos) = match &self.token {
+			// The current token is a single unquoted number.
+			Some(Token { kind: TokenKind::Argument(parts), pos }) => match parts.as_ref() {
+				[part @ ArgPart::Unquoted(ArgUnit::Literal(lit))] if part.is_unquoted_number() => {
+					(lit.len() as u32, *pos)
+				}
+				_ => return false,
+			},
+
+			_ => return false,
+		};
+
+		// And the next token is an adjacent redirection operator.
+		matches!(
+			self.cursor.peek(),
+			Some(Token { kind: TokenKind::CmdOperator(op), pos: op_pos })
+				if op.is_redirection()
+					&& op_pos.line == pos.line
+					&& op_pos.column == pos.column + number
+		)
+	}
+
+
 	/// Parse a optional file descriptor from a argument.
 	fn parse_file_descriptor(&mut self) -> Option<FileDescriptor> {
 		match &self.token {
@@ -28,21 +28,37 @@ impl<'a> Display<'a> for Error {
 	type Context = &'a symbol::Interner;
 
 	fn fmt(&self, f: &mut std::fmt::Formatter, context: Self::Context) -> std::fmt::Result {
-		match self {
-			Self::InvalidEnvAssign => "internal error: invalid env-assign".fmt(f),
+		if let Some(pos) = self.pos() {
+			write!(f, "{} - ", fmt::Show(pos, context))?;
+		}
+
+		Message(self).fmt(f, context)
+	}
+}
+
+
+/// Display only the error message, without the position.
+#[derive(Debug, Clone, Copy)]
+pub struct Message<'a>(pub &'a Error);
 
-			Self::UnexpectedEof => "unexpected end of file".fmt(f),
 
-			Self::Unexpected { token: Token { kind, pos }, expected } => {
-				write!(f, "{} - unexpected '", fmt::Show(pos, context))?;
+impl<'a, 'b> Display<'a> for Message<'b> {
+	type Context = &'a symbol::Interner;
+
+	fn fmt(&self, f: &mut std::fmt::Formatter, context: Self::Context) -> std::fmt::Result {
+		match self.0 {
+			Error::InvalidEnvAssign => "internal error: invalid env-assign".fmt(f),
+
+			Error::UnexpectedEof => "unexpected end of file".fmt(f),
+
+			Error::Unexpected { token: Token { kind, .. }, expected } => {
+				"unexpected '".fmt(f)?;
 				kind.fmt(f, context)?;
 				"', expected ".fmt(f)?;
 				expected.fmt(f, context)
 			},
 
-			Self::EmptyCommandBlock { pos } => {
-				write!(f, "{} - empty command block", fmt::Show(pos, context))
-			}
+			Error::EmptyCommandBlock { .. } => "empty command block".fmt(f),
 		}
 	}
 }
@@ -1,6 +1,7 @@
 mod fmt;
 
 use super::{SourcePos, Token, TokenKind};
+pub use fmt::Message;
 
 
 /// The kind of token the parser was expecting.
@@ -49,6 +50,22 @@ impl Error {
 	pub fn empty_command_block(pos: SourcePos) -> Self {
 		Self::EmptyCommandBlock { pos }
 	}
+
+
+	/// Display only the error message, without the position.
+	pub fn message(&self) -> Message<'_> {
+		Message(self)
+	}
+
+
+	/// The position where the error occurred, if known.
+	pub fn pos(&self) -> Option<SourcePos> {
+		match self {
+			Self::Unexpected { token, .. } => Some(token.pos),
+			Self::EmptyCommandBlock { pos } => Some(*pos),
+			Self::UnexpectedEof | Self::InvalidEnvAssign => None,
+		}
+	}
 }
 
 
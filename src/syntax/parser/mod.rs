@@ -11,6 +11,7 @@ use super::{
 		ArgPart,
 		ArgUnit,
 		Keyword,
+		Literal,
 		Token,
 		TokenKind,
 		Operator,
@@ -256,6 +257,27 @@ where
 	/// Parse a single statement.
 	fn parse_statement(&mut self) -> sync::Result<ast::Statement, Error> {
 		match self.token.take() {
+			// Let destructuring.
+			Some(Token { kind: TokenKind::Keyword(Keyword::Let), pos })
+				if matches!(
+					self.peek(),
+					Some(Token { kind: TokenKind::OpenBracket | TokenKind::OpenDict, .. })
+				) => {
+					self.step();
+
+					let pattern = self.parse_pattern()
+						.synchronize(self);
+
+					self.expect(TokenKind::Operator(Operator::Assign))
+						.with_sync(sync::Strategy::keep())
+						.synchronize(self);
+
+					// Don't synchronize here because this expression is the last part of the statement.
+					let init = self.parse_expression()?;
+
+					Ok(ast::Statement::Destructure { pattern, init, pos })
+				}
+
 			// Let.
 			Some(Token { kind: TokenKind::Keyword(Keyword::Let), .. }) => {
 				self.step();
@@ -324,6 +346,13 @@ where
 				Ok(ast::Statement::Break { pos })
 			}
 
+			// Continue.
+			Some(Token { kind: TokenKind::Keyword(Keyword::Continue), pos }) => {
+				self.step();
+
+				Ok(ast::Statement::Continue { pos })
+			}
+
 			// While.
 			Some(Token { kind: TokenKind::Keyword(Keyword::While), pos }) => {
 				self.step();
@@ -347,8 +376,19 @@ where
 			Some(Token { kind: TokenKind::Keyword(Keyword::For), .. }) => {
 				self.step();
 
-				let (identifier, pos) = self.parse_identifier()
-					.synchronize(self);
+				let (pattern, pos) = match &self.token {
+					Some(Token { kind: TokenKind::OpenBracket | TokenKind::OpenDict, pos }) => {
+						let pos = *pos;
+						(self.parse_pattern().synchronize(self), pos)
+					}
+
+					_ => {
+						let (identifier, pos) = self.parse_identifier()
+							.synchronize(self);
+
+						(ast::Pattern::Binding { identifier, pos }, pos)
+					}
+				};
 
 				self.expect(TokenKind::Keyword(Keyword::In))
 					.with_sync(sync::Strategy::skip_one())
@@ -366,7 +406,7 @@ where
 				self.expect(TokenKind::Keyword(Keyword::End))
 					.with_sync(sync::Strategy::keyword(Keyword::End))?;
 
-				Ok(ast::Statement::For { identifier, expr, block, pos })
+				Ok(ast::Statement::For { pattern, expr, block, pos })
 			}
 
 			// Expr.
@@ -376,19 +416,25 @@ where
 				// Don't synchronize here because this expression may be the last part of the statement.
 				let expr = self.parse_expression()?;
 
-				let pos = match &self.token {
-					Some(Token { kind: TokenKind::Operator(Operator::Assign), pos }) => Some(*pos),
+				let assign = match &self.token {
+					Some(Token { kind: TokenKind::Operator(Operator::Assign), pos }) => Some((None, *pos)),
+					Some(Token { kind: TokenKind::Operator(op), pos }) => op
+						.compound_assign()
+						.map(|op| (Some(op.into()), *pos)),
 					_ => None
 				};
 
-				if let Some(pos) = pos {
+				if let Some((op, pos)) = assign {
 					self.step();
 
 					// Don't synchronize here because this expression is the last part of the statement.
 					let right = self.parse_expression()?;
 
 					Ok(
-						ast::Statement::Assign { left: expr, right, pos }
+						match op {
+							Some(op) => ast::Statement::CompoundAssign { left: expr, op, right, pos },
+							None => ast::Statement::Assign { left: expr, right, pos },
+						}
 					)
 				} else {
 					Ok(ast::Statement::Expr(expr))
@@ -412,7 +458,11 @@ where
 
 		let parse_factor     = binop!(Self::parse_prefix, Operator::is_factor);
 		let parse_term       = binop!(parse_factor,     Operator::is_term);
-		let parse_concat     = binop!(parse_term,       |&op| op == Operator::Concat);
+		let parse_shift      = binop!(parse_term,       Operator::is_shift);
+		let parse_bit_and    = binop!(parse_shift,      |&op| op == Operator::BitAnd);
+		let parse_bit_xor    = binop!(parse_bit_and,    |&op| op == Operator::BitXor);
+		let parse_bit_or     = binop!(parse_bit_xor,    |&op| op == Operator::BitOr);
+		let parse_concat     = binop!(parse_bit_or,     |&op| op == Operator::Concat);
 		let parse_comparison = binop!(parse_concat,     Operator::is_comparison);
 		let parse_equality   = binop!(parse_comparison, Operator::is_equality);
 		let parse_and        = binop!(parse_equality,   |&op| op == Operator::And);
@@ -477,7 +527,34 @@ where
 
 			token => {
 				self.token = token;
-				self.parse_postfix()
+				self.parse_power()
+			}
+		}
+	}
+
+
+	/// Parse a postfix expression, optionally raised to a power. The power operator is
+	/// right associative, and binds tighter than prefix operators on its left.
+	fn parse_power(&mut self) -> sync::Result<ast::Expr, Error> {
+		let expr = self.parse_postfix()?;
+
+		match self.token.take() {
+			Some(Token { kind: TokenKind::Operator(Operator::Pow), pos }) => {
+				self.step();
+
+				let right = self.parse_prefix()?;
+
+				Ok(ast::Expr::BinaryOp {
+					left: expr.into(),
+					op: ast::BinaryOp::Pow,
+					right: right.into(),
+					pos,
+				})
+			}
+
+			token => {
+				self.token = token;
+				Ok(expr)
 			}
 		}
 	}
@@ -591,6 +668,46 @@ where
 				Ok(ast::Expr::Literal { literal: literal.into(), pos })
 			}
 
+			// Interpolated string literal.
+			Some(Token { kind: TokenKind::InterpolationStart(prefix), pos }) => {
+				self.step();
+
+				let mut parts = vec![
+					ast::Expr::Literal { literal: ast::Literal::String(prefix), pos }
+				];
+
+				loop {
+					parts.push(
+						self.parse_expression()
+							.synchronize(self)
+					);
+
+					match self.token.take() {
+						Some(Token { kind: TokenKind::InterpolationMiddle(string), pos }) => {
+							self.step();
+							parts.push(ast::Expr::Literal { literal: ast::Literal::String(string), pos });
+						}
+
+						Some(Token { kind: TokenKind::InterpolationEnd(string), pos }) => {
+							self.step();
+							parts.push(ast::Expr::Literal { literal: ast::Literal::String(string), pos });
+							break;
+						}
+
+						Some(token) => {
+							self.token = Some(token.clone());
+							return Err(Error::unexpected_msg(token, "end of interpolation"))
+								.with_sync(sync::Strategy::keep());
+						}
+
+						None => return Err(Error::unexpected_eof())
+							.with_sync(sync::Strategy::eof()),
+					}
+				}
+
+				Ok(ast::Expr::Interpolation { parts: parts.into(), pos })
+			}
+
 			// Array literal.
 			Some(Token { kind: TokenKind::OpenBracket, pos }) => {
 				self.step();
@@ -678,6 +795,29 @@ where
 				})
 			}
 
+			// Match expression.
+			Some(Token { kind: TokenKind::Keyword(Keyword::Match), pos }) => {
+				self.step();
+
+				let value = self.parse_expression()
+					.synchronize(self);
+
+				self.expect(TokenKind::Keyword(Keyword::With))
+					.with_sync(sync::Strategy::keep())
+					.synchronize(self);
+
+				let arms = self.parse_match_arms();
+
+				self.expect(TokenKind::Keyword(Keyword::End))
+					.with_sync(sync::Strategy::keyword(Keyword::End))?;
+
+				Ok(ast::Expr::Match {
+					value: value.into(),
+					arms,
+					pos,
+				})
+			}
+
 			// Parenthesis.
 			Some(Token { kind: TokenKind::OpenParens, .. }) => {
 				self.step();
@@ -722,7 +862,7 @@ where
 	#[allow(clippy::type_complexity)]
 	fn parse_function(
 		&mut self
-	) -> sync::Result<(Box<[(ast::Symbol, SourcePos)]>, ast::Block), Error> {
+	) -> sync::Result<(Box<[ast::Param]>, ast::Block), Error> {
 		let result = self.expect(TokenKind::OpenParens)
 			.with_sync(sync::Strategy::keep());
 
@@ -731,7 +871,7 @@ where
 		result.synchronize(self);
 
 		let params = self.comma_sep(
-			Self::parse_identifier,
+			Self::parse_param,
 			|token| *token == TokenKind::CloseParens,
 		);
 
@@ -754,6 +894,212 @@ where
 	}
 
 
+	/// Parse a function parameter, which may have a default value or be a rest parameter.
+	fn parse_param(&mut self) -> sync::Result<ast::Param, Error> {
+		if let Some(Token { kind: TokenKind::Ellipsis, .. }) = self.token {
+			self.step();
+
+			let (identifier, pos) = self.parse_identifier()?;
+
+			return Ok(ast::Param { identifier, kind: ast::ParamKind::Rest, pos });
+		}
+
+		let (identifier, pos) = self.parse_identifier()?;
+
+		let kind =
+			if matches!(self.token, Some(Token { kind: TokenKind::Operator(Operator::Assign), .. })) {
+				self.step();
+				ast::ParamKind::Default(self.parse_expression()?)
+			} else {
+				ast::ParamKind::Required
+			};
+
+		Ok(ast::Param { identifier, kind, pos })
+	}
+
+
+	/// Parse the arms of a match expression, stopping before the end keyword.
+	fn parse_match_arms(&mut self) -> Box<[ast::MatchArm]> {
+		let mut arms = Vec::new();
+
+		loop {
+			match self.token.take() {
+				Some(Token { kind: TokenKind::Keyword(Keyword::Case), pos }) => {
+					self.step();
+
+					let pattern = self.parse_pattern()
+						.synchronize(self);
+
+					let guard = match &self.token {
+						Some(Token { kind: TokenKind::Keyword(Keyword::If), .. }) => {
+							self.step();
+
+							let guard = self.parse_expression()
+								.synchronize(self);
+
+							Some(guard)
+						}
+
+						_ => None,
+					};
+
+					self.expect(TokenKind::Keyword(Keyword::Then))
+						.with_sync(sync::Strategy::keep())
+						.synchronize(self);
+
+					let block = self.parse_block();
+
+					arms.push(ast::MatchArm { pattern, guard, block, pos });
+				}
+
+				Some(token @ Token { kind: TokenKind::Keyword(Keyword::End), .. }) => {
+					self.token = Some(token);
+					break;
+				}
+
+				Some(token) => {
+					self.token = Some(token.clone());
+
+					Err(Error::unexpected_msg(token, "case or end"))
+						.with_sync(sync::Strategy::skip_one())
+						.synchronize(self)
+				}
+
+				None => break,
+			}
+		}
+
+		arms.into()
+	}
+
+
+	/// Parse a pattern, with an optional type annotation.
+	fn parse_pattern(&mut self) -> sync::Result<ast::Pattern, Error> {
+		let pattern = self.parse_primary_pattern()?;
+
+		match self.token.take() {
+			Some(Token { kind: TokenKind::Colon, pos }) => {
+				self.step();
+
+				let type_name = self
+					.eat(
+						|token| match token {
+							Token { kind: TokenKind::Identifier(symbol), .. } => Ok(ast::TypeName::Named(symbol)),
+							Token { kind: TokenKind::Literal(Literal::Nil), .. } => Ok(ast::TypeName::Nil),
+							Token { kind: TokenKind::Keyword(Keyword::Function), .. } => Ok(ast::TypeName::Function),
+							token => Err((Error::unexpected_msg(token.clone(), "type name"), token)),
+						}
+					)
+					.with_sync(sync::Strategy::keep())?;
+
+				Ok(
+					ast::Pattern::Type {
+						pattern: Box::new(pattern),
+						type_name,
+						pos,
+					}
+				)
+			}
+
+			token => {
+				self.token = token;
+				Ok(pattern)
+			}
+		}
+	}
+
+
+	/// Parse a pattern without type annotation.
+	fn parse_primary_pattern(&mut self) -> sync::Result<ast::Pattern, Error> {
+		match self.token.take() {
+			// Binding or wildcard.
+			Some(Token { kind: TokenKind::Identifier(identifier), pos }) => {
+				self.step();
+
+				Ok(ast::Pattern::Binding { identifier, pos })
+			}
+
+			// Basic literal.
+			Some(Token { kind: TokenKind::Literal(literal), pos }) => {
+				self.step();
+
+				Ok(ast::Pattern::Literal { literal: literal.into(), pos })
+			}
+
+			// Negative number literal.
+			Some(Token { kind: TokenKind::Operator(Operator::Minus), pos }) => {
+				self.step();
+
+				let literal = self
+					.eat(
+						|token| match token {
+							Token { kind: TokenKind::Literal(Literal::Int(int)), .. } => Ok(ast::Literal::Int(-int)),
+							Token { kind: TokenKind::Literal(Literal::Float(float)), .. } => Ok(ast::Literal::Float(-float)),
+							token => Err((Error::unexpected_msg(token.clone(), "number"), token)),
+						}
+					)
+					.with_sync(sync::Strategy::keep())?;
+
+				Ok(ast::Pattern::Literal { literal, pos })
+			}
+
+			// Array pattern.
+			Some(Token { kind: TokenKind::OpenBracket, pos }) => {
+				self.step();
+
+				let items = self.comma_sep(
+					Self::parse_pattern,
+					|token| *token == TokenKind::CloseBracket,
+				);
+
+				self.expect(TokenKind::CloseBracket)
+					.with_sync(sync::Strategy::token(TokenKind::CloseBracket))?;
+
+				Ok(ast::Pattern::Array { items, pos })
+			}
+
+			// Dict pattern.
+			Some(Token { kind: TokenKind::OpenDict, pos }) => {
+				self.step();
+
+				let items = self.comma_sep(
+					|parser| {
+						let (key, key_pos) = parser.parse_identifier()?;
+
+						let value = match &parser.token {
+							Some(Token { kind: TokenKind::Colon, .. }) => {
+								parser.step();
+								parser.parse_pattern()?
+							}
+
+							// A key without a pattern binds the value to a variable of the same name.
+							_ => ast::Pattern::Binding { identifier: key, pos: key_pos },
+						};
+
+						Ok(((key, key_pos), value))
+					},
+					|token| *token == TokenKind::CloseBracket,
+				);
+
+				self.expect(TokenKind::CloseBracket)
+					.with_sync(sync::Strategy::token(TokenKind::CloseBracket))?;
+
+				Ok(ast::Pattern::Dict { items, pos })
+			}
+
+			// Some other unexpected token.
+			Some(token) => {
+				self.token = Some(token.clone());
+				Err(Error::unexpected_msg(token, "pattern"))
+					.with_sync(sync::Strategy::keep())
+			}
+
+			None => Err(Error::unexpected_eof())
+				.with_sync(sync::Strategy::eof()),
+		}
+	}
+
+
 	/// Parse an if-else expression after the if keyword
 	/// Returns the if condition and the it+else blocks
 	fn parse_condblock(&mut self) -> sync::Result<(Box<ast::Expr>, ast::Block, ast::Block), Error> {
@@ -1,4 +1,5 @@
 use std::{
+	collections::HashMap,
 	ffi::OsStr,
 	fs::File,
 	os::unix::ffi::OsStrExt,
@@ -7,6 +8,7 @@ use std::{
 use crate::{
 	fmt::{self, Display},
 	symbol::{self, Symbol},
+	term::color,
 };
 
 
@@ -44,6 +46,48 @@ impl Source {
 
 		Ok(Self { path, contents: contents.into() })
 	}
+
+
+	/// The position right after the last character of the source code.
+	pub fn end_pos(&self) -> SourcePos {
+		let line_start = self.contents
+			.iter()
+			.rposition(|&c| c == b'\n')
+			.map_or(0, |newline| newline + 1);
+
+		let newlines = self.contents[.. line_start]
+			.iter()
+			.filter(|&&c| c == b'\n')
+			.count();
+
+		SourcePos {
+			line: 1 + newlines as u32,
+			column: (self.contents.len() - line_start) as u32,
+			path: self.path,
+			len: 0,
+		}
+	}
+}
+
+
+/// The source code of analyzed files, indexed by path, so that diagnostics may display
+/// snippets.
+#[derive(Debug, Default)]
+pub struct SourceMap(HashMap<Symbol, Box<[u8]>>);
+
+
+impl SourceMap {
+	/// Store the source code for its path.
+	/// If there was already source code for the path, it is replaced.
+	pub fn insert(&mut self, source: &Source) {
+		self.0.insert(source.path, source.contents.clone());
+	}
+
+
+	/// Get the source code for the given path, if stored.
+	pub fn get(&self, path: Symbol) -> Option<&[u8]> {
+		self.0.get(&path).map(AsRef::as_ref)
+	}
 }
 
 
@@ -53,6 +97,9 @@ pub struct SourcePos {
 	pub line: u32,
 	pub column: u32,
 	pub path: Symbol,
+	/// The length in bytes of the token at the position, limited to its line. Zero if
+	/// unknown.
+	pub len: u32,
 }
 
 
@@ -69,3 +116,91 @@ impl<'a> Display<'a> for SourcePos {
 		)
 	}
 }
+
+
+/// A snippet of the source code around a position, underlining the token at the position.
+/// Nothing is displayed if the source code is not available in the source map.
+#[derive(Debug, Clone, Copy)]
+pub struct Snippet(pub SourcePos);
+
+
+impl<'a> Display<'a> for Snippet {
+	type Context = &'a SourceMap;
+
+	fn fmt(&self, f: &mut std::fmt::Formatter, context: Self::Context) -> std::fmt::Result {
+		/// How many lines to display before the marked one.
+		const CONTEXT_LINES: u32 = 1;
+
+		let pos = self.0;
+
+		let source = match context.get(pos.path) {
+			Some(source) if pos.line > 0 => source,
+			_ => return Ok(()),
+		};
+
+		let first_line = pos.line.saturating_sub(CONTEXT_LINES).max(1);
+
+		let lines: Vec<(u32, &[u8])> = source
+			.split(|&c| c == b'\n')
+			.zip(1 ..)
+			.map(|(line, number)| (number, line.strip_suffix(b"\r").unwrap_or(line)))
+			.skip(first_line as usize - 1)
+			.take((pos.line - first_line + 1) as usize)
+			.collect();
+
+		// The source may have been replaced, e.g. in interactive sessions.
+		let line = match lines.last() {
+			Some(&(number, line)) if number == pos.line => line,
+			_ => return Ok(()),
+		};
+
+		let width = pos.line.to_string().len();
+		let bar = color::Fg(color::Blue, "|");
+
+		write!(f, "\n{:width$} {}", "", bar, width = width)?;
+
+		for (number, line) in &lines {
+			write!(
+				f,
+				"\n{} {} {}",
+				color::Fg(color::Blue, format!("{:>width$}", number, width = width)),
+				bar,
+				String::from_utf8_lossy(line)
+			)?;
+		}
+
+		// Keep tabs so that the underline is aligned, and skip UTF-8 continuation bytes.
+		let column = (pos.column as usize).min(line.len());
+		let padding: String = line[.. column]
+			.iter()
+			.filter(|&&c| is_char_start(c))
+			.map(|&c| if c == b'\t' { '\t' } else { ' ' })
+			.collect();
+
+		let end = (column + pos.len as usize).min(line.len());
+		let underline = "^".repeat(
+			line[column .. end]
+				.iter()
+				.filter(|&&c| is_char_start(c))
+				.count()
+				.max(1)
+		);
+
+		write!(
+			f,
+			"\n{:width$} {} {}{}",
+			"",
+			bar,
+			padding,
+			color::Fg(color::Red, underline),
+			width = width
+		)
+	}
+}
+
+
+/// Whether the byte starts a UTF-8 character.
+fn is_char_start(c: u8) -> bool {
+	c & 0xC0 != 0x80
+}
+
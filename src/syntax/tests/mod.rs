@@ -4,8 +4,15 @@ use std::{
 	os::unix::ffi::OsStrExt,
 };
 
-use crate::{fmt, symbol, syntax::AnalysisDisplayContext, tests};
-use super::{Analysis, Source};
+use crate::{fmt, symbol, syntax::AnalysisDisplayContext, term::color, tests};
+use super::{
+	lexer::{Cursor, Lexer},
+	Analysis,
+	Snippet,
+	Source,
+	SourceMap,
+	SourcePos,
+};
 
 
 fn test_dir<P, F>(path: P, mut check: F) -> io::Result<()>
@@ -20,6 +27,8 @@ where
 		move |path, file| {
 			let path_symbol = interner.get_or_intern(path.as_os_str().as_bytes());
 			let source = Source::from_reader(path_symbol, file)?;
+			let mut sources = SourceMap::default();
+			sources.insert(&source);
 			let analysis = Analysis::analyze(&source, &mut interner);
 
 			if !check(&analysis) {
@@ -28,6 +37,7 @@ where
 					AnalysisDisplayContext {
 						max_errors: None,
 						interner: &interner,
+						sources: &sources,
 					}
 				));
 			}
@@ -63,3 +73,66 @@ fn test_negative() -> io::Result<()> {
 		|analysis| !analysis.errors.is_empty(),
 	)
 }
+
+
+/// Render the snippet for the token at the given line and column of a source code.
+fn snippet(contents: &str, line: u32, column: u32) -> String {
+	color::disable();
+
+	let mut interner = symbol::Interner::new();
+	let path = interner.get_or_intern("<test>");
+	let source = Source::from_reader(path, contents.as_bytes()).expect("failed to read source");
+	let mut sources = SourceMap::default();
+	sources.insert(&source);
+
+	let pos = Lexer::new(Cursor::from(&source), &mut interner)
+		.map(
+			|result| match result {
+				Ok(token) => token.pos,
+				Err(error) => error.pos,
+			}
+		)
+		.find(|pos| pos.line == line && pos.column == column)
+		.unwrap_or(SourcePos { line, column, len: 0, path });
+
+	fmt::Show(Snippet(pos), &sources).to_string()
+}
+
+
+#[test]
+fn test_snippet() {
+	assert_eq!(
+		snippet("let x = 1\nlet y = undeclared + 1\n", 2, 8),
+		"\n  |\n1 | let x = 1\n2 | let y = undeclared + 1\n  |         ^^^^^^^^^^"
+	);
+
+	assert_eq!(
+		snippet("std.print(\"a \\\" b\", 1)", 1, 10),
+		"\n  |\n1 | std.print(\"a \\\" b\", 1)\n  |           ^^^^^^^^"
+	);
+
+	assert_eq!(
+		snippet("if x then\n\t1 +\nend", 2, 3),
+		"\n  |\n1 | if x then\n2 | \t1 +\n  | \t  ^"
+	);
+
+	assert_eq!(
+		snippet("let é = 1", 1, 4),
+		"\n  |\n1 | let é = 1\n  |     ^"
+	);
+
+	// Tokens spanning multiple lines are underlined until the end of their first line.
+	assert_eq!(
+		snippet("let x = \"a\nb\"", 1, 8),
+		"\n  |\n1 | let x = \"a\n  |         ^^"
+	);
+
+	// Positions past the end of the source display nothing.
+	assert_eq!(snippet("let x = 1", 3, 0), "");
+
+	// As well as sources which are not in the source map.
+	let mut interner = symbol::Interner::new();
+	let path = interner.get_or_intern("<unknown>");
+	let pos = SourcePos { line: 1, column: 0, len: 0, path };
+	assert_eq!(fmt::Show(Snippet(pos), &SourceMap::default()).to_string(), "");
+}
@@ -1,4 +1,5 @@
 use std::{
+	cell::Cell,
 	io,
 	fmt::{self, Debug, Display},
 };
@@ -7,15 +8,22 @@ pub use termion::color::{Black, Blue, Green, Red, Yellow};
 
 
 thread_local! {
-	static IS_TTY: bool = termion::is_tty(&io::stdout())
-		&& termion::is_tty(&io::stderr());
+	static IS_TTY: Cell<bool> = Cell::new(
+		termion::is_tty(&io::stdout()) && termion::is_tty(&io::stderr())
+	);
+}
+
+
+/// Disable colors and styles, even if the output is a terminal.
+pub fn disable() {
+	IS_TTY.with(|is_tty| is_tty.set(false));
 }
 
 
 macro_rules! tty_fmt {
 	($f: expr, $open: expr, $value: expr, $close: expr) => {
 		IS_TTY.with(
-			|&is_tty| if is_tty {
+			|is_tty| if is_tty.get() {
 				write!($f, "{}", $open)?;
 				$value.fmt($f)?;
 				write!($f, "{}", $close)
@@ -0,0 +1,200 @@
+use std::io::{self, Write};
+
+use termion::{
+	clear,
+	cursor,
+	event::Key,
+	input::TermRead,
+	raw::IntoRawMode,
+};
+
+
+/// The result of reading a line from the terminal.
+#[derive(Debug)]
+pub enum ReadLine {
+	/// A complete line, without the trailing newline.
+	Line(String),
+	/// The line was interrupted (Ctrl-C).
+	Interrupted,
+	/// End of input (Ctrl-D on an empty line).
+	Eof,
+}
+
+
+/// A minimal line editor for interactive sessions, with in-memory history.
+#[derive(Debug, Default)]
+pub struct Editor {
+	history: Vec<String>,
+}
+
+
+impl Editor {
+	/// Create a new editor with empty history.
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+
+	/// Add a line to the history.
+	/// Empty lines and consecutive duplicates are ignored.
+	pub fn add_history(&mut self, line: &str) {
+		let line = line.trim_end();
+
+		if line.trim_start().is_empty() || self.history.last().map(String::as_str) == Some(line) {
+			return;
+		}
+
+		self.history.push(line.to_owned());
+	}
+
+
+	/// Read a line from the terminal, displaying the given prompt.
+	/// The terminal is kept in raw mode only while the line is being edited.
+	pub fn read_line(&mut self, prompt: &str) -> io::Result<ReadLine> {
+		let stdin = io::stdin();
+		let mut stdout = io::stdout().into_raw_mode()?;
+
+		let mut line = Buffer::default();
+		// The line being edited before navigating the history.
+		let mut pending = Buffer::default();
+		let mut history_ix = self.history.len();
+
+		line.draw(&mut stdout, prompt)?;
+
+		for key in stdin.lock().keys() {
+			match key? {
+				Key::Char('\n') | Key::Char('\r') => {
+					write!(stdout, "\r\n")?;
+					return Ok(ReadLine::Line(line.chars.into_iter().collect()));
+				}
+
+				Key::Ctrl('c') => {
+					write!(stdout, "^C\r\n")?;
+					return Ok(ReadLine::Interrupted);
+				}
+
+				Key::Ctrl('d') if line.chars.is_empty() => {
+					write!(stdout, "\r\n")?;
+					return Ok(ReadLine::Eof);
+				}
+
+				Key::Ctrl('d') | Key::Delete => line.delete(),
+				Key::Backspace | Key::Ctrl('h') => line.backspace(),
+				Key::Left | Key::Ctrl('b') => line.cursor = line.cursor.saturating_sub(1),
+				Key::Right | Key::Ctrl('f') => line.cursor = (line.cursor + 1).min(line.chars.len()),
+				Key::Home | Key::Ctrl('a') => line.cursor = 0,
+				Key::End | Key::Ctrl('e') => line.cursor = line.chars.len(),
+				Key::Ctrl('u') => {
+					line.chars.drain(.. line.cursor);
+					line.cursor = 0;
+				}
+				Key::Ctrl('k') => line.chars.truncate(line.cursor),
+				Key::Ctrl('w') => line.delete_word(),
+
+				Key::Up | Key::Ctrl('p') if history_ix > 0 => {
+					if history_ix == self.history.len() {
+						pending = line;
+					}
+					history_ix -= 1;
+					line = Buffer::from(self.history[history_ix].as_str());
+				}
+
+				Key::Down | Key::Ctrl('n') if history_ix < self.history.len() => {
+					history_ix += 1;
+					line =
+						if history_ix == self.history.len() {
+							std::mem::take(&mut pending)
+						} else {
+							Buffer::from(self.history[history_ix].as_str())
+						};
+				}
+
+				// Tabs would break the cursor positioning.
+				Key::Char('\t') => (),
+				Key::Char(c) => line.insert(c),
+
+				_ => (),
+			}
+
+			line.draw(&mut stdout, prompt)?;
+		}
+
+		write!(stdout, "\r\n")?;
+		Ok(ReadLine::Eof)
+	}
+}
+
+
+/// The contents of the line being edited.
+#[derive(Debug, Default)]
+struct Buffer {
+	chars: Vec<char>,
+	/// The cursor position, in chars.
+	cursor: usize,
+}
+
+
+impl Buffer {
+	/// Insert a char at the cursor position.
+	fn insert(&mut self, c: char) {
+		self.chars.insert(self.cursor, c);
+		self.cursor += 1;
+	}
+
+
+	/// Remove the char before the cursor.
+	fn backspace(&mut self) {
+		if self.cursor > 0 {
+			self.cursor -= 1;
+			self.chars.remove(self.cursor);
+		}
+	}
+
+
+	/// Remove the char under the cursor.
+	fn delete(&mut self) {
+		if self.cursor < self.chars.len() {
+			self.chars.remove(self.cursor);
+		}
+	}
+
+
+	/// Remove the word before the cursor.
+	fn delete_word(&mut self) {
+		let mut start = self.cursor;
+
+		while start > 0 && self.chars[start - 1].is_whitespace() {
+			start -= 1;
+		}
+
+		while start > 0 && !self.chars[start - 1].is_whitespace() {
+			start -= 1;
+		}
+
+		self.chars.drain(start .. self.cursor);
+		self.cursor = start;
+	}
+
+
+	/// Redraw the line, including the prompt, and position the cursor.
+	fn draw<W: Write>(&self, out: &mut W, prompt: &str) -> io::Result<()> {
+		let line: String = self.chars.iter().collect();
+
+		write!(out, "\r{}{}{}", clear::CurrentLine, prompt, line)?;
+
+		let offset = self.chars.len() - self.cursor;
+		if offset > 0 {
+			write!(out, "{}", cursor::Left(offset as u16))?;
+		}
+
+		out.flush()
+	}
+}
+
+
+impl From<&str> for Buffer {
+	fn from(line: &str) -> Self {
+		let chars: Vec<char> = line.chars().collect();
+		Self { cursor: chars.len(), chars }
+	}
+}
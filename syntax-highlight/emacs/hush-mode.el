@@ -7,7 +7,7 @@
 
 (defvar hush-keywords
   '("let" "if" "then" "else" "elseif" "end" "for" "in" "do" "while" "function" "return"
-    "not" "and" "or" "true" "false" "nil" "break" "self"))
+    "not" "and" "or" "true" "false" "nil" "break" "continue" "self" "match" "with" "case"))
 
 (defvar hush-mode-syntax-table
   (with-syntax-table (copy-syntax-table)
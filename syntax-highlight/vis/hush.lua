@@ -16,8 +16,8 @@ local identifier = token(l.IDENTIFIER, l.word)
 
 local keyword = token(l.KEYWORD, word_match{
   'let', 'if', 'then', 'else', 'elseif', 'end', 'for', 'in', 'do', 'while',
-  'function', 'return', 'not', 'and', 'or', 'true', 'false', 'nil', 'break',
-  'self',
+  'function', 'return', 'not', 'and', 'or', 'true', 'false', 'nil', 'break', 'continue',
+  'self', 'match', 'with', 'case',
 })
 
 local operator = token(l.OPERATOR, word_match{